@@ -0,0 +1,760 @@
+//! Client-side chunking of bulk jobs larger than the per-request URL limit.
+
+use super::checkpoint::{BatchJobRecord, BulkCheckpoint, CHECKPOINT_VERSION};
+use super::results::{BulkJobResult, BulkProgress, BulkResultsOptions};
+use crate::client::AllscreenshotsClient;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// One bulk request within a [`BulkBatch`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchChunk {
+    /// The request submitted (or to be submitted) for this chunk
+    pub request: BulkRequest,
+    /// ID of the bulk job, once submitted
+    pub bulk_id: Option<String>,
+    /// Error from the last failed submission attempt
+    pub last_error: Option<String>,
+}
+
+impl BatchChunk {
+    /// Returns `true` if the chunk has been submitted.
+    pub fn is_submitted(&self) -> bool {
+        self.bulk_id.is_some()
+    }
+}
+
+/// Outcome of [`BulkBatch::submit`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSubmission {
+    /// Bulk IDs created by this call
+    pub submitted: Vec<String>,
+    /// Number of chunks that could not be submitted
+    pub failed_chunks: usize,
+}
+
+/// Merged progress of every bulk job in a [`BulkBatch`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// Total number of URLs in the batch, including unsubmitted ones
+    pub total_jobs: i32,
+    /// Number of completed jobs
+    pub completed_jobs: i32,
+    /// Number of failed jobs
+    pub failed_jobs: i32,
+    /// Number of URLs in chunks that have not been submitted
+    pub unsubmitted_jobs: i32,
+    /// Overall progress percentage (0-100)
+    pub progress: i32,
+    /// Progress of each submitted bulk job
+    pub children: Vec<BulkProgress>,
+}
+
+impl BatchProgress {
+    /// Returns `true` once every chunk is submitted and every job has finished.
+    pub fn is_finished(&self) -> bool {
+        self.unsubmitted_jobs == 0 && self.completed_jobs + self.failed_jobs >= self.total_jobs
+    }
+}
+
+/// Runs URL lists larger than [`MAX_BULK_URLS`] as several bulk jobs.
+///
+/// The URL list is split into compliant [`BulkRequest`]s sharing the same
+/// defaults and webhook, which are submitted with bounded concurrency. The
+/// batch keeps track of every child bulk ID so progress and results can be
+/// viewed as if it were a single job.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use allscreenshots_sdk::{AllscreenshotsClient, BulkBatch, BulkDefaults, BulkResultsOptions};
+/// use futures::StreamExt;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = AllscreenshotsClient::from_env()?;
+/// let urls = (0..10_000).map(|i| format!("https://example.com/page/{}", i));
+///
+/// let mut batch = BulkBatch::from_urls(client, urls)
+///     .defaults(BulkDefaults {
+///         device: Some("Desktop HD".to_string()),
+///         ..Default::default()
+///     })
+///     .concurrency(8);
+///
+/// batch.submit().await?;
+///
+/// let mut results = Box::pin(batch.results(BulkResultsOptions::default()));
+/// while let Some(result) = results.next().await {
+///     let result = result?;
+///     println!("{} -> {}", result.job.url, result.job.status);
+/// }
+///
+/// // Resubmit the entries that failed
+/// batch.resubmit_failed().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BulkBatch {
+    client: AllscreenshotsClient,
+    urls: Vec<BulkUrlRequest>,
+    defaults: Option<BulkDefaults>,
+    webhook_url: Option<String>,
+    webhook_secret: Option<String>,
+    chunk_size: usize,
+    concurrency: usize,
+    chunks: Vec<BatchChunk>,
+    jobs: Vec<BatchJobRecord>,
+    /// Position of each job ID in `jobs`
+    job_index: HashMap<String, usize>,
+    resubmitted_jobs: BTreeSet<String>,
+    checkpoint_path: Option<PathBuf>,
+}
+
+impl BulkBatch {
+    /// Create a batch for the given URL requests.
+    pub fn new(client: AllscreenshotsClient, urls: Vec<BulkUrlRequest>) -> Self {
+        Self {
+            client,
+            urls,
+            defaults: None,
+            webhook_url: None,
+            webhook_secret: None,
+            chunk_size: MAX_BULK_URLS,
+            concurrency: DEFAULT_CONCURRENCY,
+            chunks: Vec::new(),
+            jobs: Vec::new(),
+            job_index: HashMap::new(),
+            resubmitted_jobs: BTreeSet::new(),
+            checkpoint_path: None,
+        }
+    }
+
+    /// Create a batch for plain URLs using the shared defaults.
+    pub fn from_urls<I, S>(client: AllscreenshotsClient, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(client, urls.into_iter().map(BulkUrlRequest::new).collect())
+    }
+
+    /// Restore a batch from a checkpoint.
+    ///
+    /// Chunks that were already submitted keep their bulk IDs and are not
+    /// submitted again, and results marked as downloaded are not streamed
+    /// again by [`results`](Self::results).
+    pub fn resume(client: AllscreenshotsClient, checkpoint: BulkCheckpoint) -> Self {
+        Self {
+            client,
+            urls: checkpoint.pending_urls,
+            defaults: checkpoint.defaults,
+            webhook_url: checkpoint.webhook_url,
+            webhook_secret: checkpoint.webhook_secret,
+            chunk_size: checkpoint.chunk_size.clamp(1, MAX_BULK_URLS),
+            concurrency: checkpoint.concurrency.max(1),
+            chunks: checkpoint.chunks,
+            job_index: checkpoint.jobs.iter().enumerate().map(|(i, r)| (r.job_id.clone(), i)).collect(),
+            jobs: checkpoint.jobs,
+            resubmitted_jobs: checkpoint.resubmitted_jobs,
+            checkpoint_path: None,
+        }
+    }
+
+    /// Restore a batch from a checkpoint file and keep saving to it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, BulkBatch};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    /// let path = "batch-checkpoint.json";
+    ///
+    /// let mut batch = if std::path::Path::new(path).exists() {
+    ///     BulkBatch::resume_from(client, path)?
+    /// } else {
+    ///     let urls = (0..1000).map(|i| format!("https://example.com/page/{}", i));
+    ///     BulkBatch::from_urls(client, urls).checkpoint_path(path)
+    /// };
+    /// batch.submit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resume_from(client: AllscreenshotsClient, path: impl AsRef<Path>) -> Result<Self, AllscreenshotsError> {
+        let checkpoint = BulkCheckpoint::load(path.as_ref())?;
+        Ok(Self::resume(client, checkpoint).checkpoint_path(path.as_ref()))
+    }
+
+    /// Set default options applied to every chunk.
+    pub fn defaults(mut self, defaults: BulkDefaults) -> Self {
+        self.defaults = Some(defaults);
+        self
+    }
+
+    /// Set the webhook used by every chunk.
+    pub fn webhook(mut self, url: impl Into<String>, secret: Option<String>) -> Self {
+        self.webhook_url = Some(url.into());
+        self.webhook_secret = secret;
+        self
+    }
+
+    /// Set the number of URLs per bulk request (clamped to 1-100).
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.clamp(1, MAX_BULK_URLS);
+        self
+    }
+
+    /// Set how many bulk requests may be submitted or polled at once.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Save a checkpoint to this file whenever the batch state changes.
+    ///
+    /// The checkpoint is written after every [`submit`](Self::submit),
+    /// [`progress`](Self::progress), [`resubmit_failed`](Self::resubmit_failed)
+    /// and [`mark_downloaded`](Self::mark_downloaded).
+    pub fn checkpoint_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.checkpoint_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Split the URL list into compliant bulk requests.
+    ///
+    /// This is done automatically by [`submit`](Self::submit); calling it
+    /// first lets the chunks be inspected or validated.
+    pub fn plan(&mut self) -> &[BatchChunk] {
+        if !self.urls.is_empty() {
+            let urls = std::mem::take(&mut self.urls);
+            self.push_chunks(urls);
+        }
+        &self.chunks
+    }
+
+    /// The chunks planned so far.
+    pub fn chunks(&self) -> &[BatchChunk] {
+        &self.chunks
+    }
+
+    /// IDs of every bulk job submitted by this batch.
+    pub fn bulk_ids(&self) -> Vec<&str> {
+        self.chunks.iter().filter_map(|c| c.bulk_id.as_deref()).collect()
+    }
+
+    /// Entries observed so far, mapping each URL to its job.
+    pub fn jobs(&self) -> &[BatchJobRecord] {
+        &self.jobs
+    }
+
+    /// Snapshot the batch state.
+    pub fn checkpoint(&self) -> BulkCheckpoint {
+        BulkCheckpoint {
+            version: CHECKPOINT_VERSION,
+            pending_urls: self.urls.clone(),
+            chunks: self.chunks.clone(),
+            jobs: self.jobs.clone(),
+            resubmitted_jobs: self.resubmitted_jobs.clone(),
+            defaults: self.defaults.clone(),
+            webhook_url: self.webhook_url.clone(),
+            webhook_secret: self.webhook_secret.clone(),
+            chunk_size: self.chunk_size,
+            concurrency: self.concurrency,
+        }
+    }
+
+    /// Write the checkpoint to the configured [`checkpoint_path`](Self::checkpoint_path), if any.
+    pub fn save_checkpoint(&self) -> Result<(), AllscreenshotsError> {
+        match &self.checkpoint_path {
+            Some(path) => self.checkpoint().save(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Record that the result of a job has been downloaded and stored.
+    ///
+    /// Downloaded jobs are skipped by [`results`](Self::results), including
+    /// after resuming from a checkpoint. Results of bulk jobs that do not
+    /// belong to this batch are rejected.
+    pub fn mark_downloaded(
+        &mut self,
+        result: &BulkJobResult,
+        saved_to: Option<&Path>,
+    ) -> Result<(), AllscreenshotsError> {
+        let bulk_id = &result.progress.bulk_id;
+        if !self.chunks.iter().any(|c| c.bulk_id.as_ref() == Some(bulk_id)) {
+            return Err(AllscreenshotsError::ValidationError(format!(
+                "Bulk job {} is not part of this batch",
+                bulk_id
+            )));
+        }
+        let record = self.record_job(bulk_id, &result.job);
+        record.downloaded = true;
+        record.saved_to = saved_to.map(|p| p.to_string_lossy().into_owned());
+        self.save_checkpoint()
+    }
+
+    /// Submit every chunk that has not been submitted yet.
+    ///
+    /// Chunks are validated before anything is sent. Submission failures are
+    /// recorded on the chunk rather than aborting the batch, so calling
+    /// `submit` again retries only the chunks that failed.
+    pub async fn submit(&mut self) -> Result<BatchSubmission, AllscreenshotsError> {
+        self.plan();
+        for chunk in &self.chunks {
+            crate::validation::Validate::validate(&chunk.request)?;
+        }
+
+        let client = &self.client;
+        let pending: Vec<(usize, BulkRequest)> = self
+            .chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| !chunk.is_submitted())
+            .map(|(i, chunk)| (i, chunk.request.clone()))
+            .collect();
+        let mut outcomes = stream::iter(pending)
+            .map(|(i, request)| async move { (i, client.create_bulk_job(&request).await) })
+            .buffer_unordered(self.concurrency);
+
+        // Saved after every chunk, so an interrupted run never resubmits a created bulk job
+        let mut submission = BatchSubmission::default();
+        while let Some((i, outcome)) = outcomes.next().await {
+            let chunk = &mut self.chunks[i];
+            match outcome {
+                Ok(response) => {
+                    submission.submitted.push(response.id.clone());
+                    chunk.bulk_id = Some(response.id);
+                    chunk.last_error = None;
+                }
+                Err(e) => {
+                    submission.failed_chunks += 1;
+                    chunk.last_error = Some(e.to_string());
+                }
+            }
+            self.save_checkpoint()?;
+        }
+        self.save_checkpoint()?;
+        Ok(submission)
+    }
+
+    /// Fetch the status of every submitted bulk job.
+    pub async fn statuses(&self) -> Result<Vec<BulkStatusResponse>, AllscreenshotsError> {
+        let client = &self.client;
+        let results: Vec<Result<BulkStatusResponse, AllscreenshotsError>> = stream::iter(self.bulk_ids())
+            .map(|id| async move { client.get_bulk_job(id).await })
+            .buffered(self.concurrency)
+            .collect()
+            .await;
+        results.into_iter().collect()
+    }
+
+    /// Fetch the merged progress of the batch.
+    ///
+    /// The entries reported by each bulk job are recorded in [`jobs`](Self::jobs).
+    pub async fn progress(&mut self) -> Result<BatchProgress, AllscreenshotsError> {
+        let statuses = self.statuses().await?;
+        self.record_statuses(&statuses);
+        self.save_checkpoint()?;
+
+        let mut progress = BatchProgress::default();
+        for chunk in self.chunks.iter().filter(|c| !c.is_submitted()) {
+            progress.unsubmitted_jobs += chunk.request.urls.len() as i32;
+        }
+        progress.total_jobs = progress.unsubmitted_jobs;
+        for status in &statuses {
+            progress.total_jobs += status.total_jobs;
+            progress.completed_jobs += status.completed_jobs;
+            progress.failed_jobs += status.failed_jobs;
+            progress.children.push(BulkProgress::from(status));
+        }
+        if progress.total_jobs > 0 {
+            progress.progress = (progress.completed_jobs + progress.failed_jobs) * 100 / progress.total_jobs;
+        }
+        Ok(progress)
+    }
+
+    /// Stream finished entries from every submitted bulk job as one stream.
+    ///
+    /// Entries are yielded in the order they finish, across all chunks.
+    /// Entries already passed to [`mark_downloaded`](Self::mark_downloaded)
+    /// are skipped.
+    pub fn results(
+        &self,
+        options: BulkResultsOptions,
+    ) -> impl Stream<Item = Result<BulkJobResult, AllscreenshotsError>> + Send + 'static {
+        let downloaded: HashSet<String> = self
+            .jobs
+            .iter()
+            .filter(|r| r.downloaded)
+            .map(|r| r.job_id.clone())
+            .collect();
+        let streams: Vec<_> = self
+            .bulk_ids()
+            .into_iter()
+            .map(|id| Box::pin(self.client.bulk_results_skipping(id, options.clone(), downloaded.clone())))
+            .collect();
+        stream::select_all(streams)
+    }
+
+    /// Resubmit everything that did not succeed.
+    ///
+    /// Chunks that failed to submit are retried as-is. Entries that failed
+    /// inside a finished bulk job are collected, with their original per-URL
+    /// options, into new chunks. Each failed entry is resubmitted at most once.
+    pub async fn resubmit_failed(&mut self) -> Result<BatchSubmission, AllscreenshotsError> {
+        let statuses = self.statuses().await?;
+        self.record_statuses(&statuses);
+
+        let mut retry = Vec::new();
+        for status in statuses.iter().filter(|s| s.is_finished()) {
+            let Some(chunk) = self.chunks.iter().find(|c| c.bulk_id.as_deref() == Some(&status.id)) else {
+                continue;
+            };
+            for (job, url) in status.failed_entries(Some(&chunk.request), &RetryFilter::All) {
+                if !self.resubmitted_jobs.contains(&job.id) {
+                    retry.push((job.id.clone(), url));
+                }
+            }
+        }
+
+        for (job_id, _) in &retry {
+            self.resubmitted_jobs.insert(job_id.clone());
+        }
+        self.push_chunks(retry.into_iter().map(|(_, url)| url).collect());
+        self.submit().await
+    }
+
+    fn push_chunks(&mut self, urls: Vec<BulkUrlRequest>) {
+        for urls in urls.chunks(self.chunk_size) {
+            let mut request = BulkRequest::new(urls.to_vec());
+            request.defaults = self.defaults.clone();
+            request.webhook_url = self.webhook_url.clone();
+            request.webhook_secret = self.webhook_secret.clone();
+            self.chunks.push(BatchChunk {
+                request,
+                bulk_id: None,
+                last_error: None,
+            });
+        }
+    }
+
+    fn record_statuses(&mut self, statuses: &[BulkStatusResponse]) {
+        for status in statuses {
+            for job in status.jobs.iter().flatten() {
+                self.record_job(&status.id, job);
+            }
+        }
+    }
+
+    fn record_job(&mut self, bulk_id: &str, job: &BulkJobDetailInfo) -> &mut BatchJobRecord {
+        let index = match self.job_index.get(&job.id) {
+            Some(&index) => index,
+            None => {
+                self.job_index.insert(job.id.clone(), self.jobs.len());
+                self.jobs.push(BatchJobRecord {
+                    job_id: job.id.clone(),
+                    bulk_id: bulk_id.to_string(),
+                    url: job.url.clone(),
+                    status: job.status.clone(),
+                    downloaded: false,
+                    saved_to: None,
+                });
+                self.jobs.len() - 1
+            }
+        };
+        let record = &mut self.jobs[index];
+        record.status = job.status.clone();
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    fn client(server: &MockServer) -> AllscreenshotsClient {
+        AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap()
+    }
+
+    fn offline_client() -> AllscreenshotsClient {
+        AllscreenshotsClient::new("test-api-key").unwrap()
+    }
+
+    /// Responds to bulk creation with an ID derived from the first URL.
+    struct CreateBulk;
+
+    impl Respond for CreateBulk {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let first = body["urls"][0]["url"].as_str().unwrap();
+            let id = format!("bulk-{}", first.rsplit('/').next().unwrap());
+            let total = body["urls"].as_array().unwrap().len();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "id": id,
+                "status": "QUEUED",
+                "totalJobs": total,
+                "completedJobs": 0,
+                "failedJobs": 0,
+                "progress": 0
+            }))
+        }
+    }
+
+    #[test]
+    fn test_plan_splits_into_compliant_chunks() {
+        let urls = (0..250).map(|i| format!("https://example.com/{}", i));
+        let mut batch = BulkBatch::from_urls(offline_client(), urls).defaults(BulkDefaults {
+            device: Some("Desktop HD".to_string()),
+            ..Default::default()
+        });
+
+        let sizes: Vec<_> = batch.plan().iter().map(|c| c.request.urls.len()).collect();
+        assert_eq!(sizes, vec![100, 100, 50]);
+        for chunk in batch.chunks() {
+            assert!(crate::validation::Validate::validate(&chunk.request).is_ok());
+            assert_eq!(chunk.request.defaults.as_ref().unwrap().device.as_deref(), Some("Desktop HD"));
+        }
+    }
+
+    #[test]
+    fn test_chunk_size_is_clamped() {
+        let urls = (0..10).map(|i| format!("https://example.com/{}", i));
+        let mut batch = BulkBatch::from_urls(offline_client(), urls).chunk_size(3);
+        assert_eq!(batch.plan().len(), 4);
+
+        let batch = BulkBatch::from_urls(offline_client(), Vec::<String>::new()).chunk_size(1000);
+        assert_eq!(batch.chunk_size, MAX_BULK_URLS);
+    }
+
+    #[tokio::test]
+    async fn test_submit_tracks_bulk_ids_and_failures() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/bulk"))
+            .and(body_partial_json(json!({"urls": [{"url": "https://example.com/2"}]})))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "errorCode": "VALIDATION_ERROR",
+                "errorMessage": "rejected"
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/bulk"))
+            .respond_with(CreateBulk)
+            .mount(&server)
+            .await;
+
+        let urls = (0..5).map(|i| format!("https://example.com/{}", i));
+        let mut batch = BulkBatch::from_urls(client(&server), urls).chunk_size(2).concurrency(2);
+
+        let submission = batch.submit().await.unwrap();
+        assert_eq!(submission.submitted.len(), 2);
+        assert_eq!(submission.failed_chunks, 1);
+        assert!(batch.chunks()[1].last_error.as_deref().unwrap().contains("rejected"));
+
+        // Only the failed chunk is retried
+        let submission = batch.submit().await.unwrap();
+        assert_eq!(submission.submitted, vec!["bulk-2"]);
+        assert_eq!(batch.bulk_ids(), vec!["bulk-0", "bulk-2", "bulk-4"]);
+    }
+
+    #[tokio::test]
+    async fn test_progress_and_resubmit_failed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/bulk"))
+            .respond_with(CreateBulk)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-0",
+                "status": "COMPLETED",
+                "totalJobs": 2,
+                "completedJobs": 1,
+                "failedJobs": 1,
+                "progress": 100,
+                "jobs": [
+                    {"id": "j0", "url": "https://example.com/0", "status": "COMPLETED"},
+                    {"id": "j1", "url": "https://example.com/1", "status": "FAILED", "errorCode": "TIMEOUT"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-2",
+                "status": "PROCESSING",
+                "totalJobs": 1,
+                "completedJobs": 0,
+                "failedJobs": 0,
+                "progress": 0
+            })))
+            .mount(&server)
+            .await;
+
+        let urls = vec![
+            BulkUrlRequest::new("https://example.com/0"),
+            BulkUrlRequest::with_options(
+                "https://example.com/1",
+                BulkUrlOptions {
+                    full_page: Some(true),
+                    ..Default::default()
+                },
+            ),
+            BulkUrlRequest::new("https://example.com/2"),
+        ];
+        let mut batch = BulkBatch::new(client(&server), urls).chunk_size(2);
+        batch.submit().await.unwrap();
+
+        let progress = batch.progress().await.unwrap();
+        assert_eq!(progress.total_jobs, 3);
+        assert_eq!(progress.completed_jobs, 1);
+        assert_eq!(progress.failed_jobs, 1);
+        assert_eq!(progress.progress, 66);
+        assert_eq!(progress.children.len(), 2);
+        assert!(!progress.is_finished());
+
+        let submission = batch.resubmit_failed().await.unwrap();
+        assert_eq!(submission.submitted, vec!["bulk-1"]);
+        let retried = &batch.chunks()[2].request.urls[0];
+        assert_eq!(retried.url, "https://example.com/1");
+        assert_eq!(retried.options.as_ref().unwrap().full_page, Some(true));
+
+        // A failure is only resubmitted once
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-1",
+                "status": "PROCESSING",
+                "totalJobs": 1,
+                "completedJobs": 0,
+                "failedJobs": 0,
+                "progress": 0
+            })))
+            .mount(&server)
+            .await;
+        let submission = batch.resubmit_failed().await.unwrap();
+        assert!(submission.submitted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_checkpoint() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/bulk"))
+            .and(body_partial_json(json!({"urls": [{"url": "https://example.com/2"}]})))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/bulk"))
+            .respond_with(CreateBulk)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-0",
+                "status": "COMPLETED",
+                "totalJobs": 2,
+                "completedJobs": 2,
+                "failedJobs": 0,
+                "progress": 100,
+                "jobs": [
+                    {"id": "j0", "url": "https://example.com/0", "status": "COMPLETED"},
+                    {"id": "j1", "url": "https://example.com/1", "status": "COMPLETED"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint = dir.path().join("checkpoint.json");
+
+        let urls = (0..3).map(|i| format!("https://example.com/{}", i));
+        let mut batch = BulkBatch::from_urls(client(&server), urls).chunk_size(2).checkpoint_path(&checkpoint);
+        batch.submit().await.unwrap();
+        let progress = batch.progress().await.unwrap();
+        assert_eq!(progress.unsubmitted_jobs, 1);
+        let downloaded = BulkJobDetailInfo {
+            id: "j0".to_string(),
+            url: "https://example.com/0".to_string(),
+            status: "COMPLETED".to_string(),
+            result_url: None,
+            storage_url: None,
+            format: None,
+            width: None,
+            height: None,
+            file_size: None,
+            render_time_ms: None,
+            error_code: None,
+            error_message: None,
+            created_at: None,
+            completed_at: None,
+        };
+        let mut downloaded = BulkJobResult {
+            job: downloaded,
+            image: None,
+            progress: progress.children[0].clone(),
+        };
+        batch.mark_downloaded(&downloaded, Some(Path::new("out/j0.png"))).unwrap();
+        downloaded.progress.bulk_id = "bulk-other".to_string();
+        assert!(batch.mark_downloaded(&downloaded, None).is_err());
+        drop(batch);
+
+        // A new worker picks up where the previous one stopped
+        let mut batch = BulkBatch::resume_from(client(&server), &checkpoint).unwrap();
+        assert_eq!(batch.bulk_ids(), vec!["bulk-0"]);
+        assert_eq!(batch.jobs().len(), 2);
+        assert_eq!(batch.jobs()[0].saved_to.as_deref(), Some("out/j0.png"));
+
+        let submission = batch.submit().await.unwrap();
+        assert_eq!(submission.submitted, vec!["bulk-2"]);
+
+        let on_disk = BulkCheckpoint::load(&checkpoint).unwrap();
+        assert_eq!(on_disk.bulk_ids(), vec!["bulk-0", "bulk-2"]);
+
+        // Only the entries that were not downloaded yet are streamed
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-2",
+                "status": "COMPLETED",
+                "totalJobs": 1,
+                "completedJobs": 1,
+                "failedJobs": 0,
+                "progress": 100,
+                "jobs": [{"id": "j2", "url": "https://example.com/2", "status": "COMPLETED"}]
+            })))
+            .mount(&server)
+            .await;
+        let results: Vec<_> = batch.results(BulkResultsOptions::default()).collect().await;
+        let mut ids: Vec<_> = results.into_iter().map(|r| r.unwrap().job.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["j1", "j2"]);
+    }
+}
@@ -0,0 +1,155 @@
+//! Persisted state of a [`BulkBatch`](super::BulkBatch), for resuming interrupted runs.
+
+use super::batch::BatchChunk;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Version of the checkpoint file format written by this SDK.
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// A single bulk entry tracked by a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchJobRecord {
+    /// Job ID
+    pub job_id: String,
+    /// ID of the bulk job the entry belongs to
+    pub bulk_id: String,
+    /// Target URL
+    pub url: String,
+    /// Last observed job status
+    pub status: String,
+    /// Whether the result has been downloaded and stored
+    #[serde(default)]
+    pub downloaded: bool,
+    /// Where the downloaded result was stored, if recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved_to: Option<String>,
+}
+
+/// Serializable snapshot of a [`BulkBatch`](super::BulkBatch).
+///
+/// Holds the submitted bulk IDs (on each chunk), the URL to job mapping and
+/// which results were already downloaded, so a restarted worker can resume
+/// with [`BulkBatch::resume`](super::BulkBatch::resume) without resubmitting
+/// or re-downloading anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkCheckpoint {
+    /// Checkpoint file format version
+    pub version: u32,
+    /// URLs not yet split into chunks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_urls: Vec<BulkUrlRequest>,
+    /// Planned chunks and their bulk IDs
+    pub chunks: Vec<BatchChunk>,
+    /// Entries observed so far, in the order they were first seen
+    #[serde(default)]
+    pub jobs: Vec<BatchJobRecord>,
+    /// Failed job IDs that were already resubmitted
+    #[serde(default)]
+    pub resubmitted_jobs: BTreeSet<String>,
+    /// Default options applied to every chunk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<BulkDefaults>,
+    /// Webhook used by every chunk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Webhook secret used by every chunk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_secret: Option<String>,
+    /// Number of URLs per chunk
+    pub chunk_size: usize,
+    /// Maximum number of concurrent requests
+    pub concurrency: usize,
+}
+
+impl BulkCheckpoint {
+    /// Read a checkpoint from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AllscreenshotsError> {
+        let checkpoint: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(AllscreenshotsError::ConfigError(format!(
+                "Unsupported checkpoint version {} (expected {})",
+                checkpoint.version, CHECKPOINT_VERSION
+            )));
+        }
+        Ok(checkpoint)
+    }
+
+    /// Write the checkpoint to a JSON file.
+    ///
+    /// The file is written to a temporary sibling first and then renamed, so
+    /// a crash mid-write never leaves a truncated checkpoint behind.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AllscreenshotsError> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// IDs of every bulk job recorded in the checkpoint.
+    pub fn bulk_ids(&self) -> Vec<&str> {
+        self.chunks.iter().filter_map(|c| c.bulk_id.as_deref()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint() -> BulkCheckpoint {
+        BulkCheckpoint {
+            version: CHECKPOINT_VERSION,
+            pending_urls: Vec::new(),
+            chunks: vec![BatchChunk {
+                request: BulkRequest::new(vec![BulkUrlRequest::new("https://example.com")]),
+                bulk_id: Some("bulk-1".to_string()),
+                last_error: None,
+            }],
+            jobs: vec![BatchJobRecord {
+                job_id: "job-1".to_string(),
+                bulk_id: "bulk-1".to_string(),
+                url: "https://example.com".to_string(),
+                status: "COMPLETED".to_string(),
+                downloaded: true,
+                saved_to: Some("out/job-1.png".to_string()),
+            }],
+            resubmitted_jobs: BTreeSet::new(),
+            defaults: None,
+            webhook_url: None,
+            webhook_secret: None,
+            chunk_size: MAX_BULK_URLS,
+            concurrency: 4,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("batch.json");
+
+        checkpoint().save(&path).unwrap();
+        let loaded = BulkCheckpoint::load(&path).unwrap();
+        assert_eq!(loaded.bulk_ids(), vec!["bulk-1"]);
+        assert_eq!(loaded.jobs, checkpoint().jobs);
+        assert!(!dir.path().join("batch.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_unknown_version_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("batch.json");
+
+        let mut future = checkpoint();
+        future.version = CHECKPOINT_VERSION + 1;
+        future.save(&path).unwrap();
+        assert!(matches!(BulkCheckpoint::load(&path), Err(AllscreenshotsError::ConfigError(_))));
+    }
+}
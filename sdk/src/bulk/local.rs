@@ -0,0 +1,273 @@
+//! Bulk-style workloads run as concurrent single screenshots.
+
+use super::results::{BulkProgress, ProgressCallback};
+use crate::client::AllscreenshotsClient;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use futures::stream::{self, Stream, StreamExt};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Options for [`AllscreenshotsClient::screenshot_many_with`].
+#[derive(Clone)]
+pub struct ScreenshotManyOptions {
+    /// Maximum number of screenshots taken at once
+    pub concurrency: usize,
+    /// Yield results in request order instead of as they finish
+    pub ordered: bool,
+    /// Minimum delay between starting two requests
+    pub min_interval: Option<Duration>,
+    /// Called with the latest progress after every result
+    pub on_progress: Option<ProgressCallback>,
+}
+
+impl Default for ScreenshotManyOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            ordered: false,
+            min_interval: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for ScreenshotManyOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScreenshotManyOptions")
+            .field("concurrency", &self.concurrency)
+            .field("ordered", &self.ordered)
+            .field("min_interval", &self.min_interval)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl ScreenshotManyOptions {
+    /// Set the maximum number of screenshots taken at once.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Yield results in request order instead of as they finish.
+    pub fn ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Wait at least this long between starting two requests.
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = Some(interval);
+        self
+    }
+
+    /// Set a callback invoked with the latest progress after every result.
+    pub fn on_progress(mut self, callback: impl Fn(&BulkProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl AllscreenshotsClient {
+    /// Take many screenshots concurrently with the synchronous endpoint.
+    ///
+    /// A local alternative to bulk jobs for plans without the bulk endpoint,
+    /// or when results are needed sooner than the bulk queue delivers them.
+    /// Yields `(index, result)` pairs as screenshots finish, where `index` is
+    /// the position of the request in `requests`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, ScreenshotRequest};
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    /// let requests = ["https://github.com", "https://rust-lang.org"]
+    ///     .into_iter()
+    ///     .map(|url| ScreenshotRequest::builder().url(url).build())
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// let mut results = Box::pin(client.screenshot_many(requests, 4));
+    /// while let Some((index, result)) = results.next().await {
+    ///     std::fs::write(format!("{}.png", index), result?)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn screenshot_many(
+        &self,
+        requests: impl IntoIterator<Item = ScreenshotRequest>,
+        concurrency: usize,
+    ) -> impl Stream<Item = (usize, Result<Vec<u8>, AllscreenshotsError>)> + Send + 'static {
+        self.screenshot_many_with(requests, ScreenshotManyOptions::default().concurrency(concurrency))
+    }
+
+    /// Take many screenshots concurrently, with custom options.
+    ///
+    /// Every request goes through [`screenshot`](Self::screenshot), so it is
+    /// validated and retried with backoff on rate limiting like a single
+    /// call. Progress is reported in the same shape as bulk jobs, with an
+    /// empty `bulk_id`.
+    pub fn screenshot_many_with(
+        &self,
+        requests: impl IntoIterator<Item = ScreenshotRequest>,
+        options: ScreenshotManyOptions,
+    ) -> impl Stream<Item = (usize, Result<Vec<u8>, AllscreenshotsError>)> + Send + 'static {
+        let requests: Vec<_> = requests.into_iter().collect();
+        let total = requests.len() as i32;
+        let concurrency = options.concurrency.max(1);
+        let next_start = Arc::new(Mutex::new(Instant::now()));
+
+        let client = self.clone();
+        let min_interval = options.min_interval;
+        let screenshots = stream::iter(requests.into_iter().enumerate()).map(move |(index, request)| {
+            let client = client.clone();
+            let next_start = next_start.clone();
+            async move {
+                if let Some(interval) = min_interval {
+                    let start = {
+                        let mut next = next_start.lock().await;
+                        let start = (*next).max(Instant::now());
+                        *next = start + interval;
+                        start
+                    };
+                    tokio::time::sleep_until(start).await;
+                }
+                (index, client.screenshot(&request).await)
+            }
+        });
+        let screenshots = if options.ordered {
+            screenshots.buffered(concurrency).boxed()
+        } else {
+            screenshots.buffer_unordered(concurrency).boxed()
+        };
+
+        let mut progress = BulkProgress {
+            bulk_id: String::new(),
+            status: "PROCESSING".to_string(),
+            total_jobs: total,
+            completed_jobs: 0,
+            failed_jobs: 0,
+            progress: 0,
+        };
+        let on_progress = options.on_progress;
+        screenshots.inspect(move |(_, result)| {
+            if result.is_ok() {
+                progress.completed_jobs += 1;
+            } else {
+                progress.failed_jobs += 1;
+            }
+            let done = progress.completed_jobs + progress.failed_jobs;
+            progress.progress = done * 100 / total.max(1);
+            if done == total {
+                progress.status = "COMPLETED".to_string();
+            }
+            if let Some(callback) = &on_progress {
+                callback(&progress);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Echoes the requested URL back as the image body, slower for lower indexes.
+    struct Echo;
+
+    impl Respond for Echo {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let url = body["url"].as_str().unwrap().to_string();
+            let index: u64 = url.rsplit('/').next().unwrap().parse().unwrap();
+            ResponseTemplate::new(200)
+                .set_body_bytes(url.into_bytes())
+                .set_delay(Duration::from_millis(50 * (4 - index)))
+        }
+    }
+
+    fn requests(count: usize) -> Vec<ScreenshotRequest> {
+        (0..count)
+            .map(|i| ScreenshotRequest::simple(format!("https://example.com/{}", i)))
+            .collect()
+    }
+
+    async fn client() -> (MockServer, AllscreenshotsClient) {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots"))
+            .respond_with(Echo)
+            .mount(&server)
+            .await;
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_ordered_output() {
+        let (_server, client) = client().await;
+        let options = ScreenshotManyOptions::default().concurrency(4).ordered(true);
+
+        let results: Vec<_> = client.screenshot_many_with(requests(4), options).collect().await;
+        for (i, (index, result)) in results.into_iter().enumerate() {
+            assert_eq!(index, i);
+            assert_eq!(result.unwrap(), format!("https://example.com/{}", i).into_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unordered_output_and_progress() {
+        let (_server, client) = client().await;
+        let seen = Arc::new(StdMutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let options = ScreenshotManyOptions::default()
+            .concurrency(4)
+            .on_progress(move |p| recorded.lock().unwrap().push(p.clone()));
+
+        let mut batch = requests(3);
+        batch.push(ScreenshotRequest::simple("not-a-url"));
+        let results: Vec<_> = client.screenshot_many_with(batch, options).collect().await;
+
+        // The invalid request fails validation immediately, the slowest screenshot finishes last
+        assert_eq!(results[0].0, 3);
+        assert!(matches!(results[0].1, Err(AllscreenshotsError::InvalidRequest(_))));
+        assert_eq!(results[3].0, 0);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 4);
+        let last = seen.last().unwrap();
+        assert_eq!((last.completed_jobs, last.failed_jobs, last.progress), (3, 1, 100));
+        assert_eq!(last.status, "COMPLETED");
+    }
+
+    #[tokio::test]
+    async fn test_min_interval_spaces_requests() {
+        let (_server, client) = client().await;
+        let options = ScreenshotManyOptions::default()
+            .concurrency(3)
+            .min_interval(Duration::from_millis(100));
+
+        let started = Instant::now();
+        let results: Vec<_> = client.screenshot_many_with(requests(3), options).collect().await;
+        assert_eq!(results.len(), 3);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers for running bulk screenshot jobs.
+
+mod batch;
+mod checkpoint;
+mod local;
+mod results;
+
+pub use batch::*;
+pub use checkpoint::*;
+pub use local::*;
+pub use results::*;
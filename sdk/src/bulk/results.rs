@@ -0,0 +1,391 @@
+//! Following bulk screenshot jobs to completion.
+
+use crate::client::AllscreenshotsClient;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use futures::stream::{self, Stream};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Callback invoked with the latest progress after every poll.
+pub type ProgressCallback = Arc<dyn Fn(&BulkProgress) + Send + Sync>;
+
+/// Progress of a bulk job, as reported by the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkProgress {
+    /// Bulk job ID
+    pub bulk_id: String,
+    /// Current status of the bulk job
+    pub status: String,
+    /// Total number of jobs
+    pub total_jobs: i32,
+    /// Number of completed jobs
+    pub completed_jobs: i32,
+    /// Number of failed jobs
+    pub failed_jobs: i32,
+    /// Progress percentage (0-100)
+    pub progress: i32,
+}
+
+impl From<&BulkStatusResponse> for BulkProgress {
+    fn from(status: &BulkStatusResponse) -> Self {
+        Self {
+            bulk_id: status.id.clone(),
+            status: status.status.clone(),
+            total_jobs: status.total_jobs,
+            completed_jobs: status.completed_jobs,
+            failed_jobs: status.failed_jobs,
+            progress: status.progress,
+        }
+    }
+}
+
+/// A bulk entry that reached a terminal state.
+#[derive(Debug)]
+pub struct BulkJobResult {
+    /// The finished job
+    pub job: BulkJobDetailInfo,
+    /// The downloaded image, if downloading is enabled and the job succeeded
+    pub image: Option<Result<Vec<u8>, AllscreenshotsError>>,
+    /// Progress of the bulk job when this entry was observed
+    pub progress: BulkProgress,
+}
+
+/// Options for [`AllscreenshotsClient::bulk_results_with`].
+#[derive(Clone)]
+pub struct BulkResultsOptions {
+    /// Delay between status polls
+    pub poll_interval: Duration,
+    /// Download the image of every successful job
+    pub download: bool,
+    /// Give up after this long, yielding [`AllscreenshotsError::Timeout`]
+    pub timeout: Option<Duration>,
+    /// Called with the latest progress after every poll
+    pub on_progress: Option<ProgressCallback>,
+}
+
+impl Default for BulkResultsOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            download: false,
+            timeout: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for BulkResultsOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkResultsOptions")
+            .field("poll_interval", &self.poll_interval)
+            .field("download", &self.download)
+            .field("timeout", &self.timeout)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl BulkResultsOptions {
+    /// Set the delay between status polls.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Download the image of every successful job.
+    pub fn download(mut self, download: bool) -> Self {
+        self.download = download;
+        self
+    }
+
+    /// Give up after the given duration.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set a callback invoked with the latest progress after every poll.
+    pub fn on_progress(mut self, callback: impl Fn(&BulkProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+}
+
+struct BulkResultsState {
+    client: AllscreenshotsClient,
+    bulk_id: String,
+    options: BulkResultsOptions,
+    deadline: Option<Instant>,
+    seen: HashSet<String>,
+    pending: VecDeque<(BulkJobDetailInfo, BulkProgress)>,
+    polled: bool,
+    finished: bool,
+}
+
+impl AllscreenshotsClient {
+    /// Stream the entries of a bulk job as they finish.
+    ///
+    /// Each entry is yielded exactly once, when it reaches a terminal state.
+    /// The stream ends after the bulk job finishes; a failed status poll is
+    /// yielded as an error and also ends the stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, BulkRequest};
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    /// let request = BulkRequest::builder()
+    ///     .url("https://github.com")
+    ///     .url("https://rust-lang.org")
+    ///     .build()?;
+    ///
+    /// let bulk_job = client.create_bulk_job(&request).await?;
+    /// let mut results = Box::pin(client.bulk_results(&bulk_job.id));
+    /// while let Some(result) = results.next().await {
+    ///     let result = result?;
+    ///     println!(
+    ///         "{} -> {} ({}%)",
+    ///         result.job.url, result.job.status, result.progress.progress
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bulk_results(
+        &self,
+        bulk_id: &str,
+    ) -> impl Stream<Item = Result<BulkJobResult, AllscreenshotsError>> + Send + 'static {
+        self.bulk_results_with(bulk_id, BulkResultsOptions::default())
+    }
+
+    /// Stream the entries of a bulk job as they finish, with custom options.
+    ///
+    /// See [`bulk_results`](Self::bulk_results).
+    pub fn bulk_results_with(
+        &self,
+        bulk_id: &str,
+        options: BulkResultsOptions,
+    ) -> impl Stream<Item = Result<BulkJobResult, AllscreenshotsError>> + Send + 'static {
+        self.bulk_results_skipping(bulk_id, options, HashSet::new())
+    }
+
+    /// Like [`bulk_results_with`](Self::bulk_results_with), but never yields
+    /// (or downloads) the jobs in `skip`.
+    pub(crate) fn bulk_results_skipping(
+        &self,
+        bulk_id: &str,
+        options: BulkResultsOptions,
+        skip: HashSet<String>,
+    ) -> impl Stream<Item = Result<BulkJobResult, AllscreenshotsError>> + Send + 'static {
+        let state = BulkResultsState {
+            client: self.clone(),
+            bulk_id: bulk_id.to_string(),
+            deadline: options.timeout.map(|t| Instant::now() + t),
+            options,
+            seen: skip,
+            pending: VecDeque::new(),
+            polled: false,
+            finished: false,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some((job, progress)) = state.pending.pop_front() {
+                    let image = if state.options.download && job.is_success() {
+                        Some(state.client.get_job_result(&job.id).await)
+                    } else {
+                        None
+                    };
+                    return Some((Ok(BulkJobResult { job, image, progress }), state));
+                }
+
+                if state.finished {
+                    return None;
+                }
+
+                if state.polled {
+                    if let Some(deadline) = state.deadline {
+                        if Instant::now() + state.options.poll_interval > deadline {
+                            state.finished = true;
+                            return Some((Err(AllscreenshotsError::Timeout), state));
+                        }
+                    }
+                    tokio::time::sleep(state.options.poll_interval).await;
+                }
+                state.polled = true;
+
+                let status = match state.client.get_bulk_job(&state.bulk_id).await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        state.finished = true;
+                        return Some((Err(e), state));
+                    }
+                };
+
+                let progress = BulkProgress::from(&status);
+                if let Some(callback) = &state.options.on_progress {
+                    callback(&progress);
+                }
+
+                for job in status.jobs.iter().flatten() {
+                    if job.is_terminal() && state.seen.insert(job.id.clone()) {
+                        state.pending.push_back((job.clone(), progress.clone()));
+                    }
+                }
+                state.finished = status.is_finished();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn job(id: &str, status: &str) -> serde_json::Value {
+        json!({"id": id, "url": format!("https://example.com/{}", id), "status": status})
+    }
+
+    fn bulk_status(status: &str, completed: i32, failed: i32, jobs: Vec<serde_json::Value>) -> serde_json::Value {
+        json!({
+            "id": "bulk-1",
+            "status": status,
+            "totalJobs": 3,
+            "completedJobs": completed,
+            "failedJobs": failed,
+            "progress": (completed + failed) * 100 / 3,
+            "jobs": jobs,
+        })
+    }
+
+    fn client(server: &MockServer) -> AllscreenshotsClient {
+        AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bulk_results_yields_each_job_once() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(bulk_status(
+                "PROCESSING",
+                1,
+                0,
+                vec![job("a", "COMPLETED"), job("b", "PROCESSING"), job("c", "QUEUED")],
+            )))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(bulk_status(
+                "COMPLETED",
+                2,
+                1,
+                vec![job("a", "COMPLETED"), job("b", "FAILED"), job("c", "COMPLETED")],
+            )))
+            .mount(&server)
+            .await;
+
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let recorded = updates.clone();
+        let options = BulkResultsOptions::default()
+            .poll_interval(Duration::from_millis(10))
+            .on_progress(move |p| recorded.lock().unwrap().push(p.progress));
+
+        let results: Vec<_> = client(&server).bulk_results_with("bulk-1", options).collect().await;
+        let ids: Vec<_> = results.iter().map(|r| r.as_ref().unwrap().job.id.clone()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+
+        let last = results.last().unwrap().as_ref().unwrap();
+        assert_eq!(last.progress.completed_jobs, 2);
+        assert_eq!(last.progress.failed_jobs, 1);
+        assert_eq!(*updates.lock().unwrap(), vec![33, 33, 100]);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_results_downloads_successful_jobs() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(bulk_status(
+                "COMPLETED",
+                2,
+                1,
+                vec![job("a", "COMPLETED"), job("b", "FAILED"), job("c", "COMPLETED")],
+            )))
+            .mount(&server)
+            .await;
+        for id in ["a", "c"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/screenshots/jobs/{}/result", id)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(id.as_bytes().to_vec()))
+                .mount(&server)
+                .await;
+        }
+
+        let options = BulkResultsOptions::default().download(true);
+        let results: Vec<_> = client(&server).bulk_results_with("bulk-1", options).collect().await;
+        let images: Vec<_> = results
+            .into_iter()
+            .map(|r| r.unwrap().image.map(|i| i.unwrap()))
+            .collect();
+        assert_eq!(images, vec![Some(b"a".to_vec()), None, Some(b"c".to_vec())]);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_results_ends_on_poll_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "errorCode": "NOT_FOUND",
+                "errorMessage": "Bulk job not found"
+            })))
+            .mount(&server)
+            .await;
+
+        let results: Vec<_> = client(&server).bulk_results("missing").collect().await;
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            results[0],
+            Err(AllscreenshotsError::ApiError { status: 404, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_results_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(bulk_status("PROCESSING", 0, 0, vec![])))
+            .mount(&server)
+            .await;
+
+        let options = BulkResultsOptions::default()
+            .poll_interval(Duration::from_millis(20))
+            .timeout(Duration::from_millis(50));
+        let results: Vec<_> = client(&server).bulk_results_with("bulk-1", options).collect().await;
+        assert!(matches!(results.last(), Some(Err(AllscreenshotsError::Timeout))));
+    }
+}
@@ -2,15 +2,25 @@
 
 use crate::error::{AllscreenshotsError, ApiErrorResponse};
 use crate::models::*;
+use crate::quota::{QuotaGuard, QuotaTracker};
 use crate::retry::{with_retry, RetryConfig};
-use reqwest::{Client, Response, StatusCode};
+use crate::validation::Validate;
+use futures::stream::{self, Stream, TryStreamExt};
+use reqwest::{Client, Response};
+use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::env;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use url::Url;
 
 const DEFAULT_BASE_URL: &str = "https://api.allscreenshots.com";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
 const API_KEY_ENV_VAR: &str = "ALLSCREENSHOTS_API_KEY";
 const API_KEY_HEADER: &str = "X-API-Key";
+/// Number of recently created bulk requests kept for [`AllscreenshotsClient::retry_failed`].
+const REMEMBERED_BULK_REQUESTS: usize = 64;
 
 /// Client for interacting with the Allscreenshots API.
 ///
@@ -37,9 +47,12 @@ const API_KEY_HEADER: &str = "X-API-Key";
 #[derive(Debug, Clone)]
 pub struct AllscreenshotsClient {
     http_client: Client,
-    base_url: String,
+    base_url: Url,
     api_key: String,
     retry_config: RetryConfig,
+    bulk_requests: Arc<Mutex<VecDeque<(String, BulkRequest)>>>,
+    quota: Option<Arc<QuotaTracker>>,
+    capture_defaults: Option<BulkDefaults>,
 }
 
 impl AllscreenshotsClient {
@@ -88,21 +101,25 @@ impl AllscreenshotsClient {
     /// # }
     /// ```
     pub async fn screenshot(&self, request: &ScreenshotRequest) -> Result<Vec<u8>, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots", self.base_url);
+        let request = &self.with_capture_defaults(request);
+        request.validate()?;
+        let url = self.endpoint(&["v1", "screenshots"])?;
 
         let retry_config = self.retry_config.clone();
-        with_retry(&retry_config, || async {
+        let capture = with_retry(&retry_config, || async {
             let response = self
                 .http_client
-                .post(&url)
+                .post(url.clone())
                 .header(API_KEY_HEADER, &self.api_key)
                 .json(request)
                 .send()
                 .await?;
 
             self.handle_binary_response(response).await
-        })
-        .await
+        });
+        let image = self.with_quota(1, capture).await?;
+        self.record_download(image.len()).await;
+        Ok(image)
     }
 
     /// Take a screenshot asynchronously.
@@ -143,43 +160,102 @@ impl AllscreenshotsClient {
         &self,
         request: &ScreenshotRequest,
     ) -> Result<AsyncJobCreatedResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/async", self.base_url);
-        self.post_json(&url, request).await
+        let request = &self.with_capture_defaults(request);
+        request.validate()?;
+        let url = self.endpoint(&["v1", "screenshots", "async"])?;
+        self.with_quota(1, self.post_json(&url, request)).await
     }
 
     /// List all screenshot jobs.
     pub async fn list_jobs(&self) -> Result<Vec<JobResponse>, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/jobs", self.base_url);
+        let url = self.endpoint(&["v1", "screenshots", "jobs"])?;
         self.get_json(&url).await
     }
 
+    /// List one page of screenshot jobs matching `options`.
+    pub async fn list_jobs_with(&self, options: &ListOptions) -> Result<Page<JobResponse>, AllscreenshotsError> {
+        self.list_page(&["v1", "screenshots", "jobs"], options).await
+    }
+
+    /// Stream all screenshot jobs matching `options`, fetching pages as needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, ListOptions};
+    /// use futures::TryStreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    ///
+    /// let mut jobs = Box::pin(client.list_jobs_stream(ListOptions::new().status("FAILED").page_size(100)));
+    /// while let Some(job) = jobs.try_next().await? {
+    ///     println!("{} {:?}", job.id, job.error_code);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_jobs_stream(
+        &self,
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<JobResponse, AllscreenshotsError>> + Send + 'static {
+        self.list_stream(&["v1", "screenshots", "jobs"], options)
+    }
+
     /// Get the status of a screenshot job.
     pub async fn get_job(&self, job_id: &str) -> Result<JobResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/jobs/{}", self.base_url, job_id);
+        let url = self.endpoint(&["v1", "screenshots", "jobs", job_id])?;
         self.get_json(&url).await
     }
 
     /// Get the result image of a completed job.
     pub async fn get_job_result(&self, job_id: &str) -> Result<Vec<u8>, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/jobs/{}/result", self.base_url, job_id);
+        let url = self.endpoint(&["v1", "screenshots", "jobs", job_id, "result"])?;
 
         let retry_config = self.retry_config.clone();
-        with_retry(&retry_config, || async {
+        let fetch = with_retry(&retry_config, || async {
             let response = self
                 .http_client
-                .get(&url)
+                .get(url.clone())
                 .header(API_KEY_HEADER, &self.api_key)
                 .send()
                 .await?;
 
             self.handle_binary_response(response).await
-        })
-        .await
+        });
+        let image = self.with_quota(0, fetch).await?;
+        self.record_download(image.len()).await;
+        Ok(image)
+    }
+
+    /// Download a result file, such as [`ComposeResponse::url`].
+    ///
+    /// Uses the client's timeout and retry policy. Relative URLs are resolved
+    /// against the base URL, and the API key is only sent to URLs on the same
+    /// origin as the base URL, never to storage hosts or other third parties.
+    pub async fn download(&self, url: &str) -> Result<Vec<u8>, AllscreenshotsError> {
+        let target = self.base_url.join(url)?;
+        let authenticated = target.origin() == self.base_url.origin();
+
+        let retry_config = self.retry_config.clone();
+        let fetch = with_retry(&retry_config, || async {
+            let mut request = self.http_client.get(target.clone());
+            if authenticated {
+                request = request.header(API_KEY_HEADER, &self.api_key);
+            }
+            let response = request.send().await?;
+
+            self.handle_binary_response(response).await
+        });
+        let bytes = self.with_quota(0, fetch).await?;
+        self.record_download(bytes.len()).await;
+        Ok(bytes)
     }
 
     /// Cancel a screenshot job.
     pub async fn cancel_job(&self, job_id: &str) -> Result<JobResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/jobs/{}/cancel", self.base_url, job_id);
+        let url = self.endpoint(&["v1", "screenshots", "jobs", job_id, "cancel"])?;
         self.post_empty(&url).await
     }
 
@@ -209,25 +285,129 @@ impl AllscreenshotsClient {
     /// # }
     /// ```
     pub async fn create_bulk_job(&self, request: &BulkRequest) -> Result<BulkResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/bulk", self.base_url);
-        self.post_json(&url, request).await
+        let mut request = Cow::Borrowed(request);
+        if let Some(defaults) = &self.capture_defaults {
+            let merged = request.defaults.clone().unwrap_or_default().fill_unset_from(defaults);
+            request.to_mut().defaults = Some(merged);
+        }
+        let request = request.as_ref();
+        request.validate()?;
+        let url = self.endpoint(&["v1", "screenshots", "bulk"])?;
+        let response: BulkResponse = self.with_quota(request.urls.len() as u64, self.post_json(&url, request)).await?;
+        self.remember_bulk_request(&response.id, request);
+        Ok(response)
     }
 
     /// List all bulk screenshot jobs.
     pub async fn list_bulk_jobs(&self) -> Result<Vec<BulkJobSummary>, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/bulk", self.base_url);
+        let url = self.endpoint(&["v1", "screenshots", "bulk"])?;
         self.get_json(&url).await
     }
 
+    /// List one page of bulk screenshot jobs matching `options`.
+    pub async fn list_bulk_jobs_with(
+        &self,
+        options: &ListOptions,
+    ) -> Result<Page<BulkJobSummary>, AllscreenshotsError> {
+        self.list_page(&["v1", "screenshots", "bulk"], options).await
+    }
+
+    /// Stream all bulk screenshot jobs matching `options`, fetching pages as needed.
+    pub fn list_bulk_jobs_stream(
+        &self,
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<BulkJobSummary, AllscreenshotsError>> + Send + 'static {
+        self.list_stream(&["v1", "screenshots", "bulk"], options)
+    }
+
     /// Get the status of a bulk screenshot job.
     pub async fn get_bulk_job(&self, job_id: &str) -> Result<BulkStatusResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/bulk/{}", self.base_url, job_id);
+        let url = self.endpoint(&["v1", "screenshots", "bulk", job_id])?;
         self.get_json(&url).await
     }
 
+    /// Resubmit the failed entries of a bulk job as a new bulk job.
+    ///
+    /// Only entries accepted by `filter` are retried. Per-URL options,
+    /// defaults and webhook settings are taken from the original request,
+    /// which must have been created by this client (or a clone of it)
+    /// recently. For other bulk jobs this returns a validation error; use
+    /// [`retry_failed_from`](Self::retry_failed_from) to supply the original
+    /// request explicitly.
+    ///
+    /// Returns `None` if no entry matched the filter.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, ErrorCode, RetryFilter};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    ///
+    /// // Retry timeouts, but not pages that do not exist
+    /// let filter = RetryFilter::only([ErrorCode::Timeout]);
+    /// if let Some(retry) = client.retry_failed("bulk-id", &filter).await? {
+    ///     println!("Retrying as {}", retry.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retry_failed(
+        &self,
+        bulk_id: &str,
+        filter: &RetryFilter,
+    ) -> Result<Option<BulkResponse>, AllscreenshotsError> {
+        let original = self.remembered_bulk_request(bulk_id).ok_or_else(|| {
+            AllscreenshotsError::ValidationError(format!(
+                "The request of bulk job {} is not known to this client; use retry_failed_from instead",
+                bulk_id
+            ))
+        })?;
+        self.retry_failed_inner(&original, bulk_id, filter).await
+    }
+
+    /// Resubmit the failed entries of a bulk job created from `original`.
+    ///
+    /// See [`retry_failed`](Self::retry_failed).
+    pub async fn retry_failed_from(
+        &self,
+        original: &BulkRequest,
+        bulk_id: &str,
+        filter: &RetryFilter,
+    ) -> Result<Option<BulkResponse>, AllscreenshotsError> {
+        self.retry_failed_inner(original, bulk_id, filter).await
+    }
+
+    async fn retry_failed_inner(
+        &self,
+        original: &BulkRequest,
+        bulk_id: &str,
+        filter: &RetryFilter,
+    ) -> Result<Option<BulkResponse>, AllscreenshotsError> {
+        let status = self.get_bulk_job(bulk_id).await?;
+        match status.retry_request(Some(original), filter) {
+            Some(request) => self.create_bulk_job(&request).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn remember_bulk_request(&self, bulk_id: &str, request: &BulkRequest) {
+        let mut requests = self.bulk_requests.lock().unwrap_or_else(|e| e.into_inner());
+        if requests.len() == REMEMBERED_BULK_REQUESTS {
+            requests.pop_front();
+        }
+        requests.push_back((bulk_id.to_string(), request.clone()));
+    }
+
+    fn remembered_bulk_request(&self, bulk_id: &str) -> Option<BulkRequest> {
+        let requests = self.bulk_requests.lock().unwrap_or_else(|e| e.into_inner());
+        requests.iter().find(|(id, _)| id == bulk_id).map(|(_, r)| r.clone())
+    }
+
     /// Cancel a bulk screenshot job.
     pub async fn cancel_bulk_job(&self, job_id: &str) -> Result<BulkJobSummary, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/bulk/{}/cancel", self.base_url, job_id);
+        let url = self.endpoint(&["v1", "screenshots", "bulk", job_id, "cancel"])?;
         self.post_empty(&url).await
     }
 
@@ -261,8 +441,9 @@ impl AllscreenshotsClient {
     /// # }
     /// ```
     pub async fn compose(&self, request: &ComposeRequest) -> Result<ComposeResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/compose", self.base_url);
-        self.post_json(&url, request).await
+        request.validate()?;
+        let url = self.endpoint(&["v1", "screenshots", "compose"])?;
+        self.with_quota(request.capture_count() as u64, self.post_json(&url, request)).await
     }
 
     /// Compose multiple screenshots asynchronously.
@@ -270,46 +451,63 @@ impl AllscreenshotsClient {
         &self,
         request: &ComposeRequest,
     ) -> Result<ComposeJobStatusResponse, AllscreenshotsError> {
+        request.validate()?;
         let mut req = request.clone();
         req.is_async = Some(true);
-        let url = format!("{}/v1/screenshots/compose", self.base_url);
-        self.post_json(&url, &req).await
+        let url = self.endpoint(&["v1", "screenshots", "compose"])?;
+        self.with_quota(request.capture_count() as u64, self.post_json(&url, &req)).await
     }
 
     /// Preview layout placement.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, LayoutPreviewRequest, LayoutType};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    /// let request = LayoutPreviewRequest::new(LayoutType::Auto, 3).aspect_ratios(vec![1.78, 0.56, 1.0]);
+    /// let preview = client.preview_layout(&request).await?;
+    /// println!("{}x{}", preview.canvas_width, preview.canvas_height);
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn preview_layout(
         &self,
-        layout: &str,
-        image_count: i32,
-        canvas_width: Option<i32>,
-        canvas_height: Option<i32>,
-        aspect_ratios: Option<&str>,
+        request: &LayoutPreviewRequest,
     ) -> Result<LayoutPreviewResponse, AllscreenshotsError> {
-        let mut url = format!(
-            "{}/v1/screenshots/compose/preview?layout={}&image_count={}",
-            self.base_url, layout, image_count
-        );
-        if let Some(w) = canvas_width {
-            url.push_str(&format!("&canvas_width={}", w));
-        }
-        if let Some(h) = canvas_height {
-            url.push_str(&format!("&canvas_height={}", h));
-        }
-        if let Some(ar) = aspect_ratios {
-            url.push_str(&format!("&aspect_ratios={}", ar));
-        }
+        request.validate()?;
+        let mut url = self.endpoint(&["v1", "screenshots", "compose", "preview"])?;
+        url.query_pairs_mut().extend_pairs(request.query_pairs());
         self.get_json(&url).await
     }
 
     /// List all compose jobs.
     pub async fn list_compose_jobs(&self) -> Result<Vec<ComposeJobSummaryResponse>, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/compose/jobs", self.base_url);
+        let url = self.endpoint(&["v1", "screenshots", "compose", "jobs"])?;
         self.get_json(&url).await
     }
 
+    /// List one page of compose jobs matching `options`.
+    pub async fn list_compose_jobs_with(
+        &self,
+        options: &ListOptions,
+    ) -> Result<Page<ComposeJobSummaryResponse>, AllscreenshotsError> {
+        self.list_page(&["v1", "screenshots", "compose", "jobs"], options).await
+    }
+
+    /// Stream all compose jobs matching `options`, fetching pages as needed.
+    pub fn list_compose_jobs_stream(
+        &self,
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<ComposeJobSummaryResponse, AllscreenshotsError>> + Send + 'static {
+        self.list_stream(&["v1", "screenshots", "compose", "jobs"], options)
+    }
+
     /// Get the status of a compose job.
     pub async fn get_compose_job(&self, job_id: &str) -> Result<ComposeJobStatusResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/screenshots/compose/jobs/{}", self.base_url, job_id);
+        let url = self.endpoint(&["v1", "screenshots", "compose", "jobs", job_id])?;
         self.get_json(&url).await
     }
 
@@ -322,7 +520,7 @@ impl AllscreenshotsClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// # use allscreenshots_sdk::{AllscreenshotsClient, CreateScheduleRequest};
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, CreateScheduleRequest, Timezone};
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = AllscreenshotsClient::from_env()?;
@@ -331,7 +529,7 @@ impl AllscreenshotsClient {
     ///     "Daily GitHub capture",
     ///     "https://github.com",
     ///     "0 9 * * *", // Every day at 9 AM
-    /// ).with_timezone("America/New_York");
+    /// ).with_timezone(Timezone::parse("America/New_York")?);
     ///
     /// let schedule = client.create_schedule(&request).await?;
     /// println!("Schedule created: {}", schedule.id);
@@ -342,19 +540,36 @@ impl AllscreenshotsClient {
         &self,
         request: &CreateScheduleRequest,
     ) -> Result<ScheduleResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/schedules", self.base_url);
+        request.validate()?;
+        let url = self.endpoint(&["v1", "schedules"])?;
         self.post_json(&url, request).await
     }
 
     /// List all schedules.
     pub async fn list_schedules(&self) -> Result<ScheduleListResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/schedules", self.base_url);
+        let url = self.endpoint(&["v1", "schedules"])?;
         self.get_json(&url).await
     }
 
+    /// List one page of schedules matching `options`.
+    pub async fn list_schedules_with(
+        &self,
+        options: &ListOptions,
+    ) -> Result<Page<ScheduleResponse>, AllscreenshotsError> {
+        self.list_page(&["v1", "schedules"], options).await
+    }
+
+    /// Stream all schedules matching `options`, fetching pages as needed.
+    pub fn list_schedules_stream(
+        &self,
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<ScheduleResponse, AllscreenshotsError>> + Send + 'static {
+        self.list_stream(&["v1", "schedules"], options)
+    }
+
     /// Get a schedule by ID.
     pub async fn get_schedule(&self, schedule_id: &str) -> Result<ScheduleResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/schedules/{}", self.base_url, schedule_id);
+        let url = self.endpoint(&["v1", "schedules", schedule_id])?;
         self.get_json(&url).await
     }
 
@@ -364,32 +579,33 @@ impl AllscreenshotsClient {
         schedule_id: &str,
         request: &UpdateScheduleRequest,
     ) -> Result<ScheduleResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/schedules/{}", self.base_url, schedule_id);
+        request.validate()?;
+        let url = self.endpoint(&["v1", "schedules", schedule_id])?;
         self.put_json(&url, request).await
     }
 
     /// Delete a schedule.
     pub async fn delete_schedule(&self, schedule_id: &str) -> Result<(), AllscreenshotsError> {
-        let url = format!("{}/v1/schedules/{}", self.base_url, schedule_id);
+        let url = self.endpoint(&["v1", "schedules", schedule_id])?;
         self.delete(&url).await
     }
 
     /// Pause a schedule.
     pub async fn pause_schedule(&self, schedule_id: &str) -> Result<ScheduleResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/schedules/{}/pause", self.base_url, schedule_id);
+        let url = self.endpoint(&["v1", "schedules", schedule_id, "pause"])?;
         self.post_empty(&url).await
     }
 
     /// Resume a schedule.
     pub async fn resume_schedule(&self, schedule_id: &str) -> Result<ScheduleResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/schedules/{}/resume", self.base_url, schedule_id);
+        let url = self.endpoint(&["v1", "schedules", schedule_id, "resume"])?;
         self.post_empty(&url).await
     }
 
     /// Manually trigger a schedule.
     pub async fn trigger_schedule(&self, schedule_id: &str) -> Result<ScheduleResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/schedules/{}/trigger", self.base_url, schedule_id);
-        self.post_empty(&url).await
+        let url = self.endpoint(&["v1", "schedules", schedule_id, "trigger"])?;
+        self.with_quota(1, self.post_empty(&url)).await
     }
 
     /// Get the execution history of a schedule.
@@ -398,9 +614,27 @@ impl AllscreenshotsClient {
         schedule_id: &str,
         limit: Option<i32>,
     ) -> Result<ScheduleHistoryResponse, AllscreenshotsError> {
-        let mut url = format!("{}/v1/schedules/{}/history", self.base_url, schedule_id);
+        let mut url = self.endpoint(&["v1", "schedules", schedule_id, "history"])?;
         if let Some(l) = limit {
-            url.push_str(&format!("?limit={}", l));
+            url.query_pairs_mut().append_pair("limit", &l.to_string());
+        }
+        self.get_json(&url).await
+    }
+
+    /// Get one page of a schedule's execution history, filtered by `options`.
+    ///
+    /// See [`schedule_history_stream`](Self::schedule_history_stream) to page
+    /// through every execution.
+    pub async fn get_schedule_history_with(
+        &self,
+        schedule_id: &str,
+        options: &ScheduleHistoryOptions,
+    ) -> Result<ScheduleHistoryResponse, AllscreenshotsError> {
+        options.validate()?;
+        let mut url = self.endpoint(&["v1", "schedules", schedule_id, "history"])?;
+        let query = options.query_pairs();
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
         }
         self.get_json(&url).await
     }
@@ -426,13 +660,13 @@ impl AllscreenshotsClient {
     /// # }
     /// ```
     pub async fn get_usage(&self) -> Result<UsageResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/usage", self.base_url);
+        let url = self.endpoint(&["v1", "usage"])?;
         self.get_json(&url).await
     }
 
     /// Get quota status.
     pub async fn get_quota(&self) -> Result<QuotaStatusResponse, AllscreenshotsError> {
-        let url = format!("{}/v1/usage/quota", self.base_url);
+        let url = self.endpoint(&["v1", "usage", "quota"])?;
         self.get_json(&url).await
     }
 
@@ -440,7 +674,86 @@ impl AllscreenshotsClient {
     // Helper methods
     // =========================================================================
 
-    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, AllscreenshotsError> {
+    /// Apply the client's default capture options, if any, to `request`.
+    fn with_capture_defaults<'a>(&self, request: &'a ScreenshotRequest) -> Cow<'a, ScreenshotRequest> {
+        match &self.capture_defaults {
+            Some(defaults) => Cow::Owned(request.clone().fill_unset_from(defaults)),
+            None => Cow::Borrowed(request),
+        }
+    }
+
+    /// Send a request taking `screenshots` screenshots through the quota
+    /// guard, if one is configured. The screenshots are reserved before the
+    /// request is sent and given back if it fails.
+    async fn with_quota<T, Fut>(&self, screenshots: u64, request: Fut) -> Result<T, AllscreenshotsError>
+    where
+        Fut: Future<Output = Result<T, AllscreenshotsError>>,
+    {
+        let Some(tracker) = &self.quota else {
+            return request.await;
+        };
+        let reservation = tracker.check(screenshots, || self.get_quota()).await?;
+        let result = request.await;
+        if result.is_err() {
+            tracker.release(reservation).await;
+        }
+        result
+    }
+
+    async fn record_download(&self, bytes: usize) {
+        if let Some(tracker) = &self.quota {
+            tracker.record_bytes(bytes as u64).await;
+        }
+    }
+
+    /// Build an endpoint URL below the base URL.
+    ///
+    /// Every segment is percent-encoded, so an ID containing `/`, `?` or `#`
+    /// cannot change the route. Empty, `.` and `..` segments are rejected.
+    fn endpoint(&self, segments: &[&str]) -> Result<Url, AllscreenshotsError> {
+        if let Some(segment) = segments.iter().find(|s| matches!(**s, "" | "." | "..")) {
+            return Err(AllscreenshotsError::ValidationError(format!("Invalid path segment: {:?}", segment)));
+        }
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| AllscreenshotsError::ConfigError(format!("Invalid base URL: {}", self.base_url)))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+
+    async fn list_page<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &[&str],
+        options: &ListOptions,
+    ) -> Result<Page<T>, AllscreenshotsError> {
+        options.validate()?;
+        let mut url = self.endpoint(path)?;
+        let query = options.query_pairs();
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        self.get_json(&url).await
+    }
+
+    /// Lazily page through a list endpoint, one request per page.
+    fn list_stream<T: serde::de::DeserializeOwned + Send + 'static>(
+        &self,
+        path: &'static [&'static str],
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<T, AllscreenshotsError>> + Send + 'static {
+        let client = self.clone();
+        paginate(options, move |options| {
+            let client = client.clone();
+            async move {
+                let page: Page<T> = client.list_page(path, &options).await?;
+                let next = page.next_options(&options);
+                Ok((page.items, next))
+            }
+        })
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &Url) -> Result<T, AllscreenshotsError> {
         let retry_config = self.retry_config.clone();
         let url = url.to_string();
 
@@ -462,7 +775,7 @@ impl AllscreenshotsClient {
 
     async fn post_json<T: serde::de::DeserializeOwned, B: serde::Serialize>(
         &self,
-        url: &str,
+        url: &Url,
         body: &B,
     ) -> Result<T, AllscreenshotsError> {
         let retry_config = self.retry_config.clone();
@@ -488,7 +801,7 @@ impl AllscreenshotsClient {
         .await
     }
 
-    async fn post_empty<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, AllscreenshotsError> {
+    async fn post_empty<T: serde::de::DeserializeOwned>(&self, url: &Url) -> Result<T, AllscreenshotsError> {
         let retry_config = self.retry_config.clone();
         let url = url.to_string();
 
@@ -510,7 +823,7 @@ impl AllscreenshotsClient {
 
     async fn put_json<T: serde::de::DeserializeOwned, B: serde::Serialize>(
         &self,
-        url: &str,
+        url: &Url,
         body: &B,
     ) -> Result<T, AllscreenshotsError> {
         let retry_config = self.retry_config.clone();
@@ -536,7 +849,7 @@ impl AllscreenshotsClient {
         .await
     }
 
-    async fn delete(&self, url: &str) -> Result<(), AllscreenshotsError> {
+    async fn delete(&self, url: &Url) -> Result<(), AllscreenshotsError> {
         let retry_config = self.retry_config.clone();
         let url = url.to_string();
 
@@ -620,6 +933,11 @@ pub struct AllscreenshotsClientBuilder {
     base_url: Option<String>,
     timeout: Option<Duration>,
     max_retries: Option<u32>,
+    initial_retry_delay: Option<Duration>,
+    max_retry_delay: Option<Duration>,
+    proxy: Option<String>,
+    capture_defaults: Option<BulkDefaults>,
+    quota_guard: Option<QuotaGuard>,
 }
 
 impl AllscreenshotsClientBuilder {
@@ -630,6 +948,9 @@ impl AllscreenshotsClientBuilder {
     }
 
     /// Set the base URL for the API.
+    ///
+    /// May include a path prefix, e.g. `https://proxy.example.com/allscreenshots`.
+    /// Checked when the client is built.
     pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
         self.base_url = Some(base_url.into());
         self
@@ -647,6 +968,40 @@ impl AllscreenshotsClientBuilder {
         self
     }
 
+    /// Set the delay before the first retry; later retries back off exponentially.
+    pub fn initial_retry_delay(mut self, delay: Duration) -> Self {
+        self.initial_retry_delay = Some(delay);
+        self
+    }
+
+    /// Set the maximum delay between retries.
+    pub fn max_retry_delay(mut self, delay: Duration) -> Self {
+        self.max_retry_delay = Some(delay);
+        self
+    }
+
+    /// Send all requests through a proxy, e.g. `http://proxy.example.com:8080`.
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Set default capture options for screenshot and bulk requests.
+    ///
+    /// Options set on a request take precedence over these defaults.
+    pub fn capture_defaults(mut self, defaults: BulkDefaults) -> Self {
+        self.capture_defaults = Some(defaults);
+        self
+    }
+
+    /// Refuse requests locally before they would exhaust the quota.
+    ///
+    /// The guard's state is shared by all clones of the client.
+    pub fn quota_guard(mut self, guard: QuotaGuard) -> Self {
+        self.quota_guard = Some(guard);
+        self
+    }
+
     /// Build the client.
     pub fn build(self) -> Result<AllscreenshotsClient, AllscreenshotsError> {
         let api_key = match self.api_key {
@@ -661,11 +1016,16 @@ impl AllscreenshotsClientBuilder {
             ));
         }
 
-        let base_url = self.base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let base_url = parse_base_url(self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL))?;
         let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
 
-        let http_client = Client::builder()
-            .timeout(timeout)
+        let mut http_client = Client::builder().timeout(timeout);
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy.as_str())
+                .map_err(|e| AllscreenshotsError::ConfigError(format!("Invalid proxy {:?}: {}", proxy, e)))?;
+            http_client = http_client.proxy(proxy);
+        }
+        let http_client = http_client
             .build()
             .map_err(|e| AllscreenshotsError::ConfigError(format!("Failed to create HTTP client: {}", e)))?;
 
@@ -673,16 +1033,72 @@ impl AllscreenshotsClientBuilder {
         if let Some(max_retries) = self.max_retries {
             retry_config.max_retries = max_retries;
         }
+        if let Some(delay) = self.initial_retry_delay {
+            retry_config.initial_delay = delay;
+        }
+        if let Some(delay) = self.max_retry_delay {
+            retry_config.max_delay = delay;
+        }
+        if let Some(defaults) = &self.capture_defaults {
+            defaults.validate()?;
+        }
 
         Ok(AllscreenshotsClient {
             http_client,
             base_url,
             api_key,
             retry_config,
+            bulk_requests: Arc::default(),
+            quota: self.quota_guard.map(|guard| Arc::new(QuotaTracker::new(guard))),
+            capture_defaults: self.capture_defaults,
         })
     }
 }
 
+/// Parse and check a base URL: it must be an absolute http(s) URL without a
+/// query or fragment.
+fn parse_base_url(base_url: &str) -> Result<Url, AllscreenshotsError> {
+    let invalid = |reason: &str| AllscreenshotsError::ConfigError(format!("Invalid base URL {:?}: {}", base_url, reason));
+    let url = Url::parse(base_url)?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid("scheme must be http or https"));
+    }
+    if url.cannot_be_a_base() || url.host().is_none() {
+        return Err(invalid("a host is required"));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(invalid("query strings and fragments are not allowed"));
+    }
+    Ok(url)
+}
+
+/// Lazily page through an endpoint, one request per page.
+///
+/// `fetch` returns the items of the page requested with the given options,
+/// and the options for the next page, or `None` after the last one.
+pub(crate) fn paginate<O, T, F, Fut>(
+    options: O,
+    fetch: F,
+) -> impl Stream<Item = Result<T, AllscreenshotsError>> + Send + 'static
+where
+    O: Send + 'static,
+    T: Send + 'static,
+    F: Fn(O) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(Vec<T>, Option<O>), AllscreenshotsError>> + Send + 'static,
+{
+    stream::try_unfold(Some(options), move |options| {
+        let page = options.map(&fetch);
+        async move {
+            let Some(page) = page else {
+                return Ok::<_, AllscreenshotsError>(None);
+            };
+            let (items, next) = page.await?;
+            Ok(Some((stream::iter(items.into_iter().map(Ok)), next)))
+        }
+    })
+    .try_flatten()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -720,7 +1136,7 @@ mod tests {
             .build()
             .unwrap();
 
-        assert_eq!(client.base_url, "https://custom.api.com");
+        assert_eq!(client.base_url.as_str(), "https://custom.api.com/");
     }
 
     #[test]
@@ -742,4 +1158,309 @@ mod tests {
 
         assert_eq!(client.retry_config.max_retries, 5);
     }
+
+    #[test]
+    fn test_builder_rejects_invalid_base_url() {
+        let build = |base_url: &str| AllscreenshotsClient::builder().api_key("test-api-key").base_url(base_url).build();
+
+        assert!(matches!(build("api.allscreenshots.com"), Err(AllscreenshotsError::UrlError(_))));
+        for base_url in [
+            "ftp://api.example.com",
+            "mailto:support@example.com",
+            "https://api.example.com/?v=1",
+            "https://api.example.com/#v1",
+        ] {
+            assert!(matches!(build(base_url), Err(AllscreenshotsError::ConfigError(_))), "{}", base_url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ids_are_encoded_as_single_segments() {
+        use std::sync::Mutex as StdMutex;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let seen = Arc::new(StdMutex::new(Vec::new()));
+        let recorded = seen.clone();
+        Mock::given(method("DELETE"))
+            .respond_with(move |request: &Request| {
+                let query = request.url.query().map(|q| format!("?{}", q)).unwrap_or_default();
+                recorded.lock().unwrap().push(format!("{}{}", request.url.path(), query));
+                ResponseTemplate::new(204)
+            })
+            .mount(&server)
+            .await;
+
+        // A trailing slash and a path prefix on the base URL are both kept intact
+        for base_url in [server.uri(), format!("{}/", server.uri()), format!("{}/proxy/", server.uri())] {
+            let client = AllscreenshotsClient::builder()
+                .api_key("test-api-key")
+                .base_url(base_url)
+                .max_retries(0)
+                .build()
+                .unwrap();
+            for id in ["s1", "../jobs/x", "a?force=true", "b#c", "50%", "\u{e9}t\u{e9}"] {
+                client.delete_schedule(id).await.unwrap();
+            }
+            for id in ["", ".", ".."] {
+                assert!(matches!(client.delete_schedule(id).await, Err(AllscreenshotsError::ValidationError(_))));
+            }
+        }
+
+        let seen = seen.lock().unwrap();
+        let expected = [
+            "/v1/schedules/s1",
+            "/v1/schedules/..%2Fjobs%2Fx",
+            "/v1/schedules/a%3Fforce=true",
+            "/v1/schedules/b%23c",
+            "/v1/schedules/50%25",
+            "/v1/schedules/%C3%A9t%C3%A9",
+        ];
+        assert_eq!(seen[..6], expected);
+        assert_eq!(seen[6..12], expected);
+        let prefixed: Vec<_> = expected.iter().map(|p| format!("/proxy{}", p)).collect();
+        assert_eq!(seen[12..], prefixed[..]);
+    }
+
+    #[tokio::test]
+    async fn test_list_streams_follow_pages() {
+        use serde_json::json;
+        use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let job = |id: &str| json!({"id": id, "status": "FAILED", "url": "https://example.com/a&b"});
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/jobs"))
+            .and(query_param("status", "FAILED"))
+            .and(query_param("url_contains", "a&b"))
+            .and(query_param("limit", "2"))
+            .and(query_param_is_missing("cursor"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [job("j1"), job("j2")], "nextCursor": "c/2"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/jobs"))
+            .and(query_param("status", "FAILED"))
+            .and(query_param("cursor", "c/2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": [job("j3")]})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let schedule = |id: &str| json!({
+            "id": id, "name": id, "url": "https://example.com", "schedule": "0 9 * * *", "status": "ACTIVE"
+        });
+        Mock::given(method("GET"))
+            .and(path("/v1/schedules"))
+            .and(query_param_is_missing("offset"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "schedules": [schedule("s1"), schedule("s2")], "total": 3
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/schedules"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "schedules": [schedule("s3")], "total": 3
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let options = ListOptions::new().status("FAILED").url_contains("a&b").page_size(2);
+        let jobs: Vec<_> = client.list_jobs_stream(options).try_collect().await.unwrap();
+        let ids: Vec<_> = jobs.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, ["j1", "j2", "j3"]);
+
+        let schedules: Vec<_> = client.list_schedules_stream(ListOptions::new()).try_collect().await.unwrap();
+        let ids: Vec<_> = schedules.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["s1", "s2", "s3"]);
+
+        // Invalid options fail on the first poll without a request
+        let mut invalid = Box::pin(client.list_bulk_jobs_stream(ListOptions::new().page_size(0)));
+        assert!(matches!(invalid.try_next().await, Err(AllscreenshotsError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_uses_remembered_request() {
+        use serde_json::json;
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/bulk"))
+            .and(body_json(json!({
+                "urls": [{"url": "https://example.com/b", "options": {"fullPage": true}}],
+                "defaults": {"format": "jpeg"}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-2", "status": "QUEUED", "totalJobs": 1, "completedJobs": 0, "failedJobs": 0, "progress": 0
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/bulk"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-1", "status": "QUEUED", "totalJobs": 3, "completedJobs": 0, "failedJobs": 0, "progress": 0
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/bulk/bulk-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "bulk-1",
+                "status": "COMPLETED",
+                "totalJobs": 3,
+                "completedJobs": 1,
+                "failedJobs": 2,
+                "progress": 100,
+                "jobs": [
+                    {"id": "j0", "url": "https://example.com/a", "status": "COMPLETED"},
+                    {"id": "j1", "url": "https://example.com/b", "status": "FAILED", "errorCode": "TIMEOUT"},
+                    {"id": "j2", "url": "https://example.com/c", "status": "FAILED", "errorCode": "NOT_FOUND"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let request = BulkRequest::new(vec![
+            BulkUrlRequest::new("https://example.com/a"),
+            BulkUrlRequest::with_options(
+                "https://example.com/b",
+                BulkUrlOptions {
+                    full_page: Some(true),
+                    ..Default::default()
+                },
+            ),
+            BulkUrlRequest::new("https://example.com/c"),
+        ])
+        .with_defaults(BulkDefaults {
+            format: Some(ImageFormat::Jpeg),
+            ..Default::default()
+        });
+        let bulk = client.create_bulk_job(&request).await.unwrap();
+
+        let filter = RetryFilter::except([crate::error::ErrorCode::NotFound]);
+        let retry = client.clone().retry_failed(&bulk.id, &filter).await.unwrap().unwrap();
+        assert_eq!(retry.id, "bulk-2");
+
+        let nothing = client.retry_failed(&bulk.id, &RetryFilter::only([])).await.unwrap();
+        assert!(nothing.is_none());
+
+        // Requests created elsewhere must be passed explicitly
+        let unknown = client.retry_failed("bulk-unknown", &filter).await;
+        assert!(matches!(unknown, Err(AllscreenshotsError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_preview_layout_query() {
+        use serde_json::json;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/compose/preview"))
+            .and(query_param("layout", "MASONRY"))
+            .and(query_param("image_count", "2"))
+            .and(query_param("canvas_width", "1200"))
+            .and(query_param("aspect_ratios", "1.78,0.5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "layout": "MASONRY", "canvasWidth": 1200, "canvasHeight": 600, "placements": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let request = LayoutPreviewRequest::new(LayoutType::Masonry, 2)
+            .aspect_ratios(vec![1.78, 0.5])
+            .canvas_width(1200);
+        let preview = client.preview_layout(&request).await.unwrap();
+        assert_eq!(preview.canvas_width, 1200);
+
+        // Invalid requests are rejected before reaching the API
+        let invalid = LayoutPreviewRequest::new(LayoutType::Grid, 0);
+        assert!(matches!(
+            client.preview_layout(&invalid).await,
+            Err(AllscreenshotsError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_quota_guard_refuses_requests() {
+        use serde_json::json;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/usage/quota"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tier": "FREE",
+                "screenshots": {"limit": 100, "used": 98, "remaining": 2, "percentUsed": 98},
+                "bandwidth": {
+                    "limitBytes": 1000, "limitFormatted": "1 KB", "usedBytes": 0, "usedFormatted": "0 B",
+                    "remainingBytes": 1000, "remainingFormatted": "1 KB", "percentUsed": 0
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"image".to_vec()))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots/async"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({"error": "bad_request", "message": "nope"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .quota_guard(QuotaGuard::default())
+            .build()
+            .unwrap();
+
+        let request = ScreenshotRequest::builder().url("https://example.com").build().unwrap();
+        // A failed request gives its reserved screenshot back
+        assert!(matches!(client.screenshot_async(&request).await, Err(AllscreenshotsError::ApiError { .. })));
+        client.screenshot(&request).await.unwrap();
+        client.clone().screenshot(&request).await.unwrap();
+        // Both screenshots are counted locally against the cached quota
+        assert!(matches!(
+            client.screenshot(&request).await,
+            Err(AllscreenshotsError::QuotaExceeded(_))
+        ));
+    }
 }
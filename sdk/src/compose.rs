@@ -0,0 +1,319 @@
+//! Waiting for async compose jobs and downloading their results.
+
+use crate::client::AllscreenshotsClient;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[cfg(feature = "compose-local")]
+mod local;
+#[cfg(feature = "compose-local")]
+pub use local::{LocalCompositor, LocalImage};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Callback invoked with the latest progress of a compose job after every poll.
+pub type ComposeProgressCallback = Arc<dyn Fn(&ComposeProgress) + Send + Sync>;
+
+/// Progress of a compose job, as reported by the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeProgress {
+    /// Compose job ID
+    pub job_id: String,
+    /// Current status
+    pub status: String,
+    /// Progress percentage (0-100)
+    pub progress: i32,
+    /// Total number of captures
+    pub total_captures: Option<i32>,
+    /// Number of completed captures
+    pub completed_captures: Option<i32>,
+}
+
+impl From<&ComposeJobStatusResponse> for ComposeProgress {
+    fn from(status: &ComposeJobStatusResponse) -> Self {
+        Self {
+            job_id: status.job_id.clone(),
+            status: status.status.clone(),
+            progress: status.progress.unwrap_or(0),
+            total_captures: status.total_captures,
+            completed_captures: status.completed_captures,
+        }
+    }
+}
+
+/// A finished compose job together with the downloaded image.
+#[derive(Debug, Clone)]
+pub struct ComposeOutput {
+    /// Final status of the job
+    pub job: ComposeJobStatusResponse,
+    /// The composed image
+    pub image: Vec<u8>,
+}
+
+/// Options for [`AllscreenshotsClient::wait_for_compose_with`].
+#[derive(Clone)]
+pub struct ComposeWaitOptions {
+    /// Delay between status polls
+    pub poll_interval: Duration,
+    /// Give up after this long, returning [`AllscreenshotsError::Timeout`]
+    pub timeout: Option<Duration>,
+    /// Called with the latest progress after every poll
+    pub on_progress: Option<ComposeProgressCallback>,
+}
+
+impl Default for ComposeWaitOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for ComposeWaitOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComposeWaitOptions")
+            .field("poll_interval", &self.poll_interval)
+            .field("timeout", &self.timeout)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl ComposeWaitOptions {
+    /// Set the delay between status polls.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Give up after the given duration.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set a callback invoked with the latest progress after every poll.
+    pub fn on_progress(mut self, callback: impl Fn(&ComposeProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl AllscreenshotsClient {
+    /// Wait for an async compose job to finish and download the composed image.
+    ///
+    /// A job that fails or is cancelled is returned as an
+    /// [`AllscreenshotsError::ApiError`] carrying the job's error code.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, CaptureItem, ComposeRequest, ComposeWaitOptions};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    /// let request = ComposeRequest::builder()
+    ///     .capture(CaptureItem::new("https://github.com").with_device("Desktop HD"))
+    ///     .capture(CaptureItem::new("https://github.com").with_device("iPhone 14"))
+    ///     .async_mode(true)
+    ///     .build()?;
+    ///
+    /// let job = client.compose_async(&request).await?;
+    /// let options = ComposeWaitOptions::default()
+    ///     .on_progress(|p| println!("{}: {}%", p.status, p.progress));
+    /// let output = client.wait_for_compose_with(&job.job_id, options).await?;
+    /// std::fs::write("composed.png", &output.image)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_compose(&self, job_id: &str) -> Result<ComposeOutput, AllscreenshotsError> {
+        self.wait_for_compose_with(job_id, ComposeWaitOptions::default()).await
+    }
+
+    /// Wait for an async compose job to finish, with custom options.
+    ///
+    /// See [`wait_for_compose`](Self::wait_for_compose).
+    pub async fn wait_for_compose_with(
+        &self,
+        job_id: &str,
+        options: ComposeWaitOptions,
+    ) -> Result<ComposeOutput, AllscreenshotsError> {
+        let deadline = options.timeout.map(|t| Instant::now() + t);
+
+        let job = loop {
+            let status = self.get_compose_job(job_id).await?;
+            if let Some(callback) = &options.on_progress {
+                callback(&ComposeProgress::from(&status));
+            }
+
+            match status.status.parse::<JobStatus>() {
+                Ok(JobStatus::Completed) => break status,
+                Ok(s) if s.is_terminal() => {
+                    let code = status.error_code.as_deref().or(match s {
+                        JobStatus::Cancelled => Some("CANCELLED"),
+                        _ => None,
+                    });
+                    let message = status
+                        .error_message
+                        .unwrap_or_else(|| format!("Compose job {} ended with status {}", job_id, status.status));
+                    return Err(AllscreenshotsError::from_api_response(200, code, &message));
+                }
+                _ => {}
+            }
+
+            if let Some(deadline) = deadline {
+                if Instant::now() + options.poll_interval > deadline {
+                    return Err(AllscreenshotsError::Timeout);
+                }
+            }
+            tokio::time::sleep(options.poll_interval).await;
+        };
+
+        let url = job.result.as_ref().and_then(|r| r.url.as_deref()).ok_or_else(|| {
+            AllscreenshotsError::from_api_response(200, None, &format!("Compose job {} has no result URL", job_id))
+        })?;
+        let image = self.download(url).await?;
+        Ok(ComposeOutput { job, image })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    fn client(server: &MockServer) -> AllscreenshotsClient {
+        AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(1)
+            .build()
+            .unwrap()
+    }
+
+    fn options() -> ComposeWaitOptions {
+        ComposeWaitOptions::default().poll_interval(Duration::from_millis(10))
+    }
+
+    #[tokio::test]
+    async fn test_wait_and_download() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/compose/jobs/c1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jobId": "c1", "status": "PROCESSING", "progress": 50, "totalCaptures": 2, "completedCaptures": 1
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/compose/jobs/c1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jobId": "c1",
+                "status": "COMPLETED",
+                "progress": 100,
+                "totalCaptures": 2,
+                "completedCaptures": 2,
+                "result": {"url": "/v1/files/c1.png"}
+            })))
+            .mount(&server)
+            .await;
+        // The first download attempt fails and is retried
+        Mock::given(method("GET"))
+            .and(path("/v1/files/c1.png"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/files/c1.png"))
+            .and(header("X-API-Key", "test-api-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"composed".to_vec()))
+            .mount(&server)
+            .await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let output = client(&server)
+            .wait_for_compose_with("c1", options().on_progress(move |p| recorded.lock().unwrap().push(p.clone())))
+            .await
+            .unwrap();
+
+        assert_eq!(output.image, b"composed");
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].completed_captures, Some(1));
+        assert_eq!(seen[1].progress, 100);
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_is_an_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/compose/jobs/c2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jobId": "c2", "status": "FAILED", "errorCode": "TIMEOUT", "errorMessage": "Capture timed out"
+            })))
+            .mount(&server)
+            .await;
+
+        match client(&server).wait_for_compose_with("c2", options()).await {
+            Err(AllscreenshotsError::ApiError { code, message, .. }) => {
+                assert_eq!(code, ErrorCode::Timeout);
+                assert_eq!(message, "Capture timed out");
+            }
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/screenshots/compose/jobs/c3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"jobId": "c3", "status": "QUEUED"})))
+            .mount(&server)
+            .await;
+
+        let result = client(&server)
+            .wait_for_compose_with("c3", options().timeout(Duration::from_millis(50)))
+            .await;
+        assert!(matches!(result, Err(AllscreenshotsError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_download_keeps_api_key_on_origin() {
+        let api = MockServer::start().await;
+        let storage = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/result.png"))
+            .and(header_exists("X-API-Key"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&storage)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/result.png"))
+            .respond_with(|request: &Request| {
+                assert!(!request.headers.contains_key("X-API-Key"));
+                ResponseTemplate::new(200).set_body_bytes(b"image".to_vec())
+            })
+            .mount(&storage)
+            .await;
+
+        let image = client(&api)
+            .download(&format!("{}/result.png", storage.uri()))
+            .await
+            .unwrap();
+        assert_eq!(image, b"image");
+    }
+}
@@ -0,0 +1,457 @@
+//! Local compositor for building composed images from screenshots already on hand.
+//!
+//! Available with the `compose-local` feature.
+
+use crate::error::AllscreenshotsError;
+use crate::layout::{LayoutSpec, DEFAULT_CELL_WIDTH};
+use crate::models::*;
+use crate::validation::Validate;
+use ab_glyph::{FontArc, PxScale};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageEncoder, Rgba, RgbaImage};
+
+const DEFAULT_JPEG_QUALITY: u8 = 90;
+const DEFAULT_FONT_SIZE: i32 = 14;
+
+/// A screenshot to place in a locally composed image.
+#[derive(Debug, Clone)]
+pub struct LocalImage {
+    /// Encoded image bytes (PNG, JPEG or WebP)
+    pub data: Vec<u8>,
+    /// Label drawn with the image when labels are enabled
+    pub label: Option<String>,
+}
+
+impl LocalImage {
+    /// Create an image from encoded bytes.
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            data: data.into(),
+            label: None,
+        }
+    }
+
+    /// Set the label.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// Renders composed images locally, following a [`ComposeOutputConfig`].
+///
+/// Useful when compose quota runs out or the screenshots were already taken.
+/// Placements come from the offline [`LayoutSpec`] engine; spacing, padding,
+/// background, borders, shadows, thumbnail width and labels are applied as
+/// closely to the `compose` endpoint as practical. WebP output is lossless.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use allscreenshots_sdk::{ComposeOutputConfig, LayoutType, LocalCompositor, LocalImage};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let images = vec![
+///     LocalImage::new(std::fs::read("desktop.png")?).with_label("Desktop"),
+///     LocalImage::new(std::fs::read("mobile.png")?).with_label("Mobile"),
+/// ];
+/// let output = ComposeOutputConfig::builder()
+///     .layout(LayoutType::Horizontal)
+///     .spacing(20)
+///     .background("#f5f5f5")
+///     .build()?;
+///
+/// let compositor = LocalCompositor::new().with_font(std::fs::read("DejaVuSans.ttf")?)?;
+/// std::fs::write("composed.png", compositor.render(&images, &output)?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LocalCompositor {
+    font: Option<FontArc>,
+}
+
+impl LocalCompositor {
+    /// Create a compositor without a font; labels are not available.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given TrueType or OpenType font for labels.
+    pub fn with_font(mut self, font: Vec<u8>) -> Result<Self, AllscreenshotsError> {
+        let font = FontArc::try_from_vec(font)
+            .map_err(|e| AllscreenshotsError::ConfigError(format!("Invalid font: {}", e)))?;
+        self.font = Some(font);
+        Ok(self)
+    }
+
+    /// Compose the images and encode the result in the configured format.
+    pub fn render(&self, images: &[LocalImage], output: &ComposeOutputConfig) -> Result<Vec<u8>, AllscreenshotsError> {
+        let canvas = self.render_image(images, output)?;
+        encode(canvas, output)
+    }
+
+    /// Compose the images without encoding the result.
+    pub fn render_image(
+        &self,
+        images: &[LocalImage],
+        output: &ComposeOutputConfig,
+    ) -> Result<RgbaImage, AllscreenshotsError> {
+        output.validate()?;
+        let decoded = images
+            .iter()
+            .enumerate()
+            .map(|(i, image)| {
+                image::load_from_memory(&image.data)
+                    .map(|d| d.to_rgba8())
+                    .map_err(|e| AllscreenshotsError::ImageError(format!("Image {}: {}", i, e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let labels = output.labels.as_ref().filter(|l| l.enabled.unwrap_or(true));
+        let font = match labels {
+            Some(_) if images.iter().any(|i| i.label.is_some()) => Some(self.font.as_ref().ok_or_else(|| {
+                AllscreenshotsError::ConfigError("Labels need a font; use LocalCompositor::with_font".to_string())
+            })?),
+            _ => None,
+        };
+        let font_size = labels.and_then(|l| l.font_size).unwrap_or(DEFAULT_FONT_SIZE).max(1);
+        let label_band = if font.is_some() { font_size + font_size / 2 } else { 0 };
+
+        // Labeled cells are taller by the label band, so lay them out with the combined aspect ratio
+        let cell = output.thumbnail_width.unwrap_or(DEFAULT_CELL_WIDTH) as f64;
+        let ratios = decoded
+            .iter()
+            .zip(images)
+            .map(|(image, source)| {
+                let height = cell * image.height().max(1) as f64 / image.width().max(1) as f64;
+                let band = if source.label.is_some() { label_band as f64 } else { 0.0 };
+                cell / (height + band)
+            })
+            .collect();
+        let preview = LayoutSpec::from_output(output, images.len()).aspect_ratios(ratios).compute()?;
+
+        let background = to_rgba(output.background.unwrap_or(Color::WHITE));
+        let mut canvas = RgbaImage::from_pixel(
+            preview.canvas_width.max(1) as u32,
+            preview.canvas_height.max(1) as u32,
+            background,
+        );
+
+        let border = output.border.as_ref().filter(|b| b.enabled.unwrap_or(true));
+        let radius = border.and_then(|b| b.radius).unwrap_or(0);
+
+        if let Some(shadow) = output.shadow.as_ref().filter(|s| s.enabled.unwrap_or(true)) {
+            draw_shadows(&mut canvas, &preview.placements, shadow, radius);
+        }
+
+        let position = labels.and_then(|l| l.position).unwrap_or_default();
+        for (placement, (image, source)) in preview.placements.iter().zip(decoded.iter().zip(images)) {
+            let band = if source.label.is_some() { label_band.min(placement.height - 1).max(0) } else { 0 };
+            let image_top = match position {
+                LabelPosition::Top => placement.y + band,
+                LabelPosition::Bottom => placement.y,
+            };
+            let (width, height) = (placement.width, placement.height - band);
+            if width > 0 && height > 0 {
+                let fitted = DynamicImage::ImageRgba8(image.clone())
+                    .resize_to_fill(width as u32, height as u32, FilterType::Triangle)
+                    .to_rgba8();
+                let rect = Rect::new(placement.x, image_top, width, height);
+                for (x, y, pixel) in fitted.enumerate_pixels() {
+                    let (cx, cy) = (rect.x + x as i32, rect.y + y as i32);
+                    if rect.contains_rounded(cx, cy, radius) {
+                        blend(&mut canvas, cx, cy, *pixel);
+                    }
+                }
+                if let Some(border) = border {
+                    draw_border(&mut canvas, rect, border, radius);
+                }
+            }
+
+            if let (Some(font), Some(text), Some(labels)) = (font, &source.label, labels) {
+                let band_top = match position {
+                    LabelPosition::Top => placement.y,
+                    LabelPosition::Bottom => placement.y + placement.height - band,
+                };
+                let color = to_rgba(labels.color.unwrap_or(Color::BLACK));
+                let scale = PxScale::from(font_size as f32);
+                let (text_width, text_height) = imageproc::drawing::text_size(scale, font, text);
+                let x = placement.x + (placement.width - text_width as i32) / 2;
+                let y = band_top + (band - text_height as i32) / 2;
+                imageproc::drawing::draw_text_mut(&mut canvas, color, x, y, scale, font, text);
+            }
+        }
+
+        Ok(canvas)
+    }
+}
+
+fn encode(canvas: RgbaImage, output: &ComposeOutputConfig) -> Result<Vec<u8>, AllscreenshotsError> {
+    let image_error = |e: image::ImageError| AllscreenshotsError::ImageError(e.to_string());
+    let (width, height) = canvas.dimensions();
+    let mut bytes = Vec::new();
+    match output.format.clone().unwrap_or_default() {
+        ImageFormat::Png => PngEncoder::new(&mut bytes)
+            .write_image(&canvas, width, height, image::ExtendedColorType::Rgba8)
+            .map_err(image_error)?,
+        ImageFormat::Jpeg | ImageFormat::Jpg => {
+            let quality = output.quality.map(|q| q.clamp(1, 100) as u8).unwrap_or(DEFAULT_JPEG_QUALITY);
+            let rgb = DynamicImage::ImageRgba8(canvas).to_rgb8();
+            JpegEncoder::new_with_quality(&mut bytes, quality)
+                .write_image(&rgb, width, height, image::ExtendedColorType::Rgb8)
+                .map_err(image_error)?
+        }
+        ImageFormat::Webp => WebPEncoder::new_lossless(&mut bytes)
+            .write_image(&canvas, width, height, image::ExtendedColorType::Rgba8)
+            .map_err(image_error)?,
+        ImageFormat::Pdf => {
+            return Err(AllscreenshotsError::ValidationError(
+                "PDF output is not supported by the local compositor".to_string(),
+            ))
+        }
+        ImageFormat::Unknown(format) => {
+            return Err(AllscreenshotsError::ValidationError(format!("Unknown output format '{}'", format)))
+        }
+    }
+    Ok(bytes)
+}
+
+fn draw_shadows(canvas: &mut RgbaImage, placements: &[PlacementPreview], shadow: &ShadowConfig, radius: i32) {
+    let color = to_rgba(shadow.color.unwrap_or(Color::rgba(0, 0, 0, 0x40)));
+    let (dx, dy) = (shadow.offset_x.unwrap_or(0), shadow.offset_y.unwrap_or(4));
+    let mut layer = RgbaImage::new(canvas.width(), canvas.height());
+    for placement in placements {
+        let rect = Rect::new(placement.x + dx, placement.y + dy, placement.width, placement.height);
+        for y in rect.y.max(0)..(rect.y + rect.height).min(canvas.height() as i32) {
+            for x in rect.x.max(0)..(rect.x + rect.width).min(canvas.width() as i32) {
+                if rect.contains_rounded(x, y, radius) {
+                    layer.put_pixel(x as u32, y as u32, color);
+                }
+            }
+        }
+    }
+
+    let blur = shadow.blur.unwrap_or(8);
+    if blur > 0 {
+        layer = imageops::blur(&layer, blur as f32 / 2.0);
+    }
+    for (x, y, pixel) in layer.enumerate_pixels() {
+        blend(canvas, x as i32, y as i32, *pixel);
+    }
+}
+
+fn draw_border(canvas: &mut RgbaImage, rect: Rect, border: &BorderConfig, radius: i32) {
+    let width = border.width.unwrap_or(1);
+    if width <= 0 {
+        return;
+    }
+    let color = to_rgba(border.color.unwrap_or(Color::BLACK));
+    let inner = Rect::new(rect.x + width, rect.y + width, rect.width - 2 * width, rect.height - 2 * width);
+    let inner_radius = (radius - width).max(0);
+    for y in rect.y..rect.y + rect.height {
+        for x in rect.x..rect.x + rect.width {
+            if rect.contains_rounded(x, y, radius) && !inner.contains_rounded(x, y, inner_radius) {
+                blend(canvas, x, y, color);
+            }
+        }
+    }
+}
+
+/// Alpha-blend `pixel` over the canvas, ignoring coordinates outside it.
+fn blend(canvas: &mut RgbaImage, x: i32, y: i32, pixel: Rgba<u8>) {
+    if x < 0 || y < 0 || x >= canvas.width() as i32 || y >= canvas.height() as i32 {
+        return;
+    }
+    let alpha = pixel[3] as u32;
+    if alpha == 0 {
+        return;
+    }
+    let target = canvas.get_pixel_mut(x as u32, y as u32);
+    for c in 0..3 {
+        target[c] = ((pixel[c] as u32 * alpha + target[c] as u32 * (255 - alpha)) / 255) as u8;
+    }
+    target[3] = (alpha + target[3] as u32 * (255 - alpha) / 255) as u8;
+}
+
+fn to_rgba(color: Color) -> Rgba<u8> {
+    Rgba([color.r, color.g, color.b, color.a])
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Rect {
+    fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Returns `true` if the pixel lies inside the rectangle with rounded corners.
+    fn contains_rounded(&self, x: i32, y: i32, radius: i32) -> bool {
+        if self.width <= 0 || self.height <= 0 {
+            return false;
+        }
+        if x < self.x || y < self.y || x >= self.x + self.width || y >= self.y + self.height {
+            return false;
+        }
+        let radius = radius.min(self.width / 2).min(self.height / 2);
+        if radius <= 0 {
+            return true;
+        }
+
+        // Distance from the pixel centre to the nearest corner centre, in corner regions only
+        let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+        let r = radius as f64;
+        let cx = px.clamp(self.x as f64 + r, (self.x + self.width) as f64 - r);
+        let cy = py.clamp(self.y as f64 + r, (self.y + self.height) as f64 - r);
+        (px - cx).powi(2) + (py - cy).powi(2) <= r * r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn solid(width: u32, height: u32, color: [u8; 4]) -> LocalImage {
+        let mut bytes = Vec::new();
+        RgbaImage::from_pixel(width, height, Rgba(color))
+            .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+            .unwrap();
+        LocalImage::new(bytes)
+    }
+
+    fn output() -> ComposeOutputConfig {
+        ComposeOutputConfig {
+            layout: Some(LayoutType::Grid),
+            columns: Some(2),
+            spacing: Some(10),
+            padding: Some(5),
+            thumbnail_width: Some(100),
+            background: Some(Color::BLACK),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_grid_matches_layout() {
+        let images = [solid(160, 90, [255, 0, 0, 255]), solid(160, 90, [0, 0, 255, 255])];
+        let canvas = LocalCompositor::new().render_image(&images, &output()).unwrap();
+
+        let preview = LayoutSpec::from_output(&output(), 2).compute().unwrap();
+        assert_eq!(canvas.dimensions(), (preview.canvas_width as u32, preview.canvas_height as u32));
+        assert_eq!(canvas.get_pixel(50, 30), &Rgba([255, 0, 0, 255]));
+        assert_eq!(canvas.get_pixel(160, 30), &Rgba([0, 0, 255, 255]));
+        // Padding and spacing show the background
+        assert_eq!(canvas.get_pixel(2, 2), &Rgba([0, 0, 0, 255]));
+        assert_eq!(canvas.get_pixel(109, 30), &Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_border_and_shadow() {
+        let mut output = output();
+        output.border = Some(BorderConfig {
+            enabled: Some(true),
+            width: Some(3),
+            color: Some(Color::WHITE),
+            radius: None,
+        });
+        output.shadow = Some(ShadowConfig {
+            enabled: Some(true),
+            blur: Some(0),
+            color: Some(Color::rgb(0, 255, 0)),
+            offset_x: Some(0),
+            offset_y: Some(4),
+        });
+        let images = [solid(160, 90, [255, 0, 0, 255])];
+        let canvas = LocalCompositor::new().render_image(&images, &output).unwrap();
+
+        assert_eq!(canvas.get_pixel(6, 30), &Rgba([255, 255, 255, 255]));
+        assert_eq!(canvas.get_pixel(50, 30), &Rgba([255, 0, 0, 255]));
+        // The shadow shows below the image
+        assert_eq!(canvas.get_pixel(50, 5 + 56 + 2), &Rgba([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn test_output_formats() {
+        let images = [solid(32, 18, [255, 0, 0, 255])];
+        for (format, expected) in [
+            (ImageFormat::Png, image::ImageFormat::Png),
+            (ImageFormat::Jpeg, image::ImageFormat::Jpeg),
+            (ImageFormat::Webp, image::ImageFormat::WebP),
+        ] {
+            let output = ComposeOutputConfig {
+                format: Some(format),
+                ..output()
+            };
+            let bytes = LocalCompositor::new().render(&images, &output).unwrap();
+            assert_eq!(image::guess_format(&bytes).unwrap(), expected);
+        }
+
+        let pdf = ComposeOutputConfig {
+            format: Some(ImageFormat::Pdf),
+            ..output()
+        };
+        assert!(LocalCompositor::new().render(&images, &pdf).is_err());
+    }
+
+    #[test]
+    fn test_labels_need_a_font() {
+        let output = ComposeOutputConfig {
+            labels: Some(LabelConfig {
+                enabled: Some(true),
+                ..Default::default()
+            }),
+            ..output()
+        };
+        let images = [solid(32, 18, [255, 0, 0, 255]).with_label("Desktop")];
+        assert!(matches!(
+            LocalCompositor::new().render(&images, &output),
+            Err(AllscreenshotsError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_labels_are_drawn_in_their_band() {
+        let font = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/fonts/DejaVuSansMono.ttf"));
+        let compositor = LocalCompositor::new().with_font(font.unwrap()).unwrap();
+        let labels = ComposeOutputConfig {
+            labels: Some(LabelConfig {
+                font_size: Some(20),
+                color: Some(Color::WHITE),
+                position: Some(LabelPosition::Top),
+                ..Default::default()
+            }),
+            ..output()
+        };
+        let red = [255, 0, 0, 255];
+        let plain = compositor.render_image(&[solid(160, 90, red), solid(160, 90, red)], &output()).unwrap();
+        let images = [solid(160, 90, red).with_label("Desktop"), solid(160, 90, red).with_label("Mobile")];
+        let canvas = compositor.render_image(&images, &labels).unwrap();
+
+        // Each row grows by the label band of 1.5 times the font size
+        assert!(canvas.height().abs_diff(plain.height() + 30) <= 1, "{} vs {}", canvas.height(), plain.height());
+        // The band above the first image holds white text on the black background
+        let band: Vec<_> = (5..105).flat_map(|x| (5..35).map(move |y| (x, y))).collect();
+        assert!(band.iter().any(|&(x, y)| canvas.get_pixel(x, y).0[..3].iter().all(|c| *c > 128)));
+        assert!(band.iter().all(|&(x, y)| canvas.get_pixel(x, y) != &Rgba(red)));
+        assert_eq!(canvas.get_pixel(50, 50), &Rgba(red));
+    }
+
+    #[test]
+    fn test_invalid_image() {
+        let images = [LocalImage::new(b"not an image".to_vec())];
+        assert!(matches!(
+            LocalCompositor::new().render(&images, &output()),
+            Err(AllscreenshotsError::ImageError(_))
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+//! Configuration files with named profiles.
+//!
+//! ```toml
+//! default_profile = "prod"
+//!
+//! [profiles.prod]
+//! api_key = "..."
+//! timeout_secs = 60
+//! retry = { max_retries = 5, initial_delay_ms = 1000 }
+//! defaults = { device = "Desktop HD", blockAds = true }
+//!
+//! [profiles.staging]
+//! api_key = "..."
+//! base_url = "https://staging.api.allscreenshots.com"
+//! proxy = "http://proxy.internal:8080"
+//!
+//! [profiles.local]
+//! api_key = "test"
+//! base_url = "http://localhost:8080"
+//! retry = { max_retries = 0 }
+//! ```
+//!
+//! `defaults` takes the same camelCase options as [`BulkDefaults`].
+
+use crate::client::{AllscreenshotsClient, AllscreenshotsClientBuilder};
+use crate::error::AllscreenshotsError;
+use crate::models::BulkDefaults;
+use crate::validation::{field_path, Validate, ValidationErrors};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable with the path of the configuration file.
+pub const CONFIG_ENV_VAR: &str = "ALLSCREENSHOTS_CONFIG";
+/// Environment variable selecting the profile.
+pub const PROFILE_ENV_VAR: &str = "ALLSCREENSHOTS_PROFILE";
+
+const DEFAULT_PROFILE: &str = "default";
+const API_KEY_ENV_VAR: &str = "ALLSCREENSHOTS_API_KEY";
+const BASE_URL_ENV_VAR: &str = "ALLSCREENSHOTS_BASE_URL";
+const TIMEOUT_ENV_VAR: &str = "ALLSCREENSHOTS_TIMEOUT";
+const PROXY_ENV_VAR: &str = "ALLSCREENSHOTS_PROXY";
+const MAX_RETRIES_ENV_VAR: &str = "ALLSCREENSHOTS_MAX_RETRIES";
+
+/// A configuration file: named profiles and the one to use by default.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Profile used when none is requested, `default` if unset
+    pub default_profile: Option<String>,
+    /// Profiles by name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Client settings of one profile. Unset fields keep the client defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// API key
+    pub api_key: Option<String>,
+    /// Base URL of the API
+    pub base_url: Option<String>,
+    /// Request timeout in seconds
+    pub timeout_secs: Option<u64>,
+    /// Retry behavior
+    #[serde(default)]
+    pub retry: RetrySettings,
+    /// Proxy for all requests
+    pub proxy: Option<String>,
+    /// Default capture options for screenshot and bulk requests
+    pub defaults: Option<BulkDefaults>,
+}
+
+/// Retry settings of a [`Profile`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetrySettings {
+    /// Maximum number of retries
+    pub max_retries: Option<u32>,
+    /// Delay before the first retry in milliseconds
+    pub initial_delay_ms: Option<u64>,
+    /// Maximum delay between retries in milliseconds
+    pub max_delay_ms: Option<u64>,
+}
+
+impl ConfigFile {
+    /// Path of the configuration file: `$ALLSCREENSHOTS_CONFIG`, or
+    /// `~/.config/allscreenshots/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(CONFIG_ENV_VAR) {
+            return Some(PathBuf::from(path));
+        }
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+        Some(Path::new(&home).join(".config").join("allscreenshots").join("config.toml"))
+    }
+
+    /// Load a configuration file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AllscreenshotsError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            AllscreenshotsError::ConfigError(format!("Cannot read config file {}: {}", path.display(), e))
+        })?;
+        Self::from_toml_str(&contents)
+    }
+
+    /// Parse a configuration file, validating the default options of every profile.
+    pub fn from_toml_str(contents: &str) -> Result<Self, AllscreenshotsError> {
+        let config: Self = toml::from_str(contents)
+            .map_err(|e| AllscreenshotsError::ConfigError(format!("Invalid TOML config: {}", e)))?;
+        let mut errors = ValidationErrors::new();
+        for (name, profile) in &config.profiles {
+            let path = format!("profiles.{}", name);
+            profile.defaults.collect_violations(&field_path(&path, "defaults"), &mut errors);
+        }
+        errors.into_result()?;
+        Ok(config)
+    }
+
+    /// The profile called `name`, or the default profile if `name` is `None`.
+    ///
+    /// A missing default profile is treated as empty, so environment
+    /// variables alone are enough; a missing named profile is an error.
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile, AllscreenshotsError> {
+        let requested = name.or(self.default_profile.as_deref());
+        match self.profiles.get(requested.unwrap_or(DEFAULT_PROFILE)) {
+            Some(profile) => Ok(profile.clone()),
+            None => match requested {
+                Some(name) => Err(AllscreenshotsError::ConfigError(format!(
+                    "Unknown profile '{}'; available profiles: {}",
+                    name,
+                    self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+                ))),
+                None => Ok(Profile::default()),
+            },
+        }
+    }
+}
+
+impl Profile {
+    /// Override settings from the `ALLSCREENSHOTS_API_KEY`,
+    /// `ALLSCREENSHOTS_BASE_URL`, `ALLSCREENSHOTS_TIMEOUT` (seconds),
+    /// `ALLSCREENSHOTS_PROXY` and `ALLSCREENSHOTS_MAX_RETRIES` environment variables.
+    pub fn with_env_overrides(self) -> Result<Self, AllscreenshotsError> {
+        self.with_overrides(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+    }
+
+    fn with_overrides(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self, AllscreenshotsError> {
+        if let Some(api_key) = var(API_KEY_ENV_VAR) {
+            self.api_key = Some(api_key);
+        }
+        if let Some(base_url) = var(BASE_URL_ENV_VAR) {
+            self.base_url = Some(base_url);
+        }
+        if let Some(timeout) = var(TIMEOUT_ENV_VAR) {
+            self.timeout_secs = Some(parse_number(TIMEOUT_ENV_VAR, &timeout)?);
+        }
+        if let Some(proxy) = var(PROXY_ENV_VAR) {
+            self.proxy = Some(proxy);
+        }
+        if let Some(max_retries) = var(MAX_RETRIES_ENV_VAR) {
+            self.retry.max_retries = Some(parse_number(MAX_RETRIES_ENV_VAR, &max_retries)?);
+        }
+        Ok(self)
+    }
+
+    /// A client builder with this profile's settings.
+    pub fn builder(&self) -> AllscreenshotsClientBuilder {
+        let mut builder = AllscreenshotsClient::builder();
+        if let Some(api_key) = &self.api_key {
+            builder = builder.api_key(api_key);
+        }
+        if let Some(base_url) = &self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(timeout) = self.timeout_secs {
+            builder = builder.timeout(Duration::from_secs(timeout));
+        }
+        if let Some(max_retries) = self.retry.max_retries {
+            builder = builder.max_retries(max_retries);
+        }
+        if let Some(delay) = self.retry.initial_delay_ms {
+            builder = builder.initial_retry_delay(Duration::from_millis(delay));
+        }
+        if let Some(delay) = self.retry.max_delay_ms {
+            builder = builder.max_retry_delay(Duration::from_millis(delay));
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(defaults) = &self.defaults {
+            builder = builder.capture_defaults(defaults.clone());
+        }
+        builder
+    }
+}
+
+impl AllscreenshotsClient {
+    /// Create a client from the configuration file.
+    ///
+    /// Uses the profile named by `ALLSCREENSHOTS_PROFILE`, or the file's
+    /// default profile. See [`from_config_profile`](Self::from_config_profile).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::AllscreenshotsClient;
+    /// // ALLSCREENSHOTS_PROFILE=staging ALLSCREENSHOTS_TIMEOUT=120 cargo run
+    /// let client = AllscreenshotsClient::from_config()?;
+    /// # Ok::<(), allscreenshots_sdk::AllscreenshotsError>(())
+    /// ```
+    pub fn from_config() -> Result<Self, AllscreenshotsError> {
+        let profile = std::env::var(PROFILE_ENV_VAR).ok().filter(|p| !p.is_empty());
+        Self::from_config_profile_inner(profile.as_deref())
+    }
+
+    /// Create a client from a profile of the configuration file.
+    ///
+    /// The file is `$ALLSCREENSHOTS_CONFIG` or
+    /// `~/.config/allscreenshots/config.toml`; without one, only environment
+    /// variables are used. Environment variables override the profile.
+    pub fn from_config_profile(profile: &str) -> Result<Self, AllscreenshotsError> {
+        Self::from_config_profile_inner(Some(profile))
+    }
+
+    fn from_config_profile_inner(profile: Option<&str>) -> Result<Self, AllscreenshotsError> {
+        let config = match ConfigFile::default_path() {
+            // An explicitly configured path must exist
+            Some(path) if path.exists() || std::env::var_os(CONFIG_ENV_VAR).is_some() => ConfigFile::load(path)?,
+            _ => ConfigFile::default(),
+        };
+        config.profile(profile)?.with_env_overrides()?.builder().build()
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, AllscreenshotsError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| AllscreenshotsError::ConfigError(format!("{} must be a whole number, got {:?}", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        default_profile = "prod"
+
+        [profiles.prod]
+        api_key = "prod-key"
+        timeout_secs = 30
+        retry = { max_retries = 5, initial_delay_ms = 100 }
+        defaults = { device = "Desktop HD", fullPage = true }
+
+        [profiles.local]
+        api_key = "test"
+        base_url = "http://localhost:8080"
+    "#;
+
+    #[test]
+    fn test_profiles() {
+        let config = ConfigFile::from_toml_str(CONFIG).unwrap();
+        let prod = config.profile(None).unwrap();
+        assert_eq!(prod.api_key.as_deref(), Some("prod-key"));
+        assert_eq!(prod.retry.max_retries, Some(5));
+        assert_eq!(prod.defaults.unwrap().full_page, Some(true));
+        assert_eq!(config.profile(Some("local")).unwrap().base_url.as_deref(), Some("http://localhost:8080"));
+        assert!(matches!(config.profile(Some("staging")), Err(AllscreenshotsError::ConfigError(_))));
+
+        // Without a default profile, environment variables alone are enough
+        assert!(ConfigFile::default().profile(None).unwrap().api_key.is_none());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let errors = match ConfigFile::from_toml_str("[profiles.prod]\ndefaults = { quality = 0 }") {
+            Err(AllscreenshotsError::InvalidRequest(errors)) => errors,
+            other => panic!("expected validation errors, got {:?}", other),
+        };
+        assert!(errors.has_field("profiles.prod.defaults.quality"));
+        assert!(matches!(
+            ConfigFile::from_toml_str("[profiles.prod]\napi_kee = \"typo\""),
+            Err(AllscreenshotsError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let profile = ConfigFile::from_toml_str(CONFIG).unwrap().profile(None).unwrap();
+        let env = |name: &str| match name {
+            BASE_URL_ENV_VAR => Some("https://staging.example.com".to_string()),
+            TIMEOUT_ENV_VAR => Some("120".to_string()),
+            _ => None,
+        };
+        let profile = profile.with_overrides(env).unwrap();
+        assert_eq!(profile.api_key.as_deref(), Some("prod-key"));
+        assert_eq!(profile.base_url.as_deref(), Some("https://staging.example.com"));
+        assert_eq!(profile.timeout_secs, Some(120));
+        assert!(profile.builder().build().is_ok());
+
+        let invalid = Profile::default().with_overrides(|name| (name == TIMEOUT_ENV_VAR).then(|| "soon".to_string()));
+        assert!(matches!(invalid, Err(AllscreenshotsError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_capture_defaults_are_applied() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/screenshots"))
+            .and(body_partial_json(serde_json::json!({"device": "Desktop HD", "fullPage": false})))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"image".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut profile = ConfigFile::from_toml_str(CONFIG).unwrap().profile(None).unwrap();
+        profile.base_url = Some(server.uri());
+        let client = profile.builder().build().unwrap();
+        let request = crate::ScreenshotRequest::builder().url("https://example.com").full_page(false).build().unwrap();
+        assert_eq!(client.screenshot(&request).await.unwrap(), b"image");
+    }
+}
@@ -1,5 +1,6 @@
 //! Error types for the Allscreenshots SDK.
 
+use crate::validation::ValidationErrors;
 use thiserror::Error;
 
 /// Error codes returned by the Allscreenshots API.
@@ -75,6 +76,10 @@ pub enum AllscreenshotsError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    /// Client-side validation found one or more invalid fields
+    #[error("Invalid request: {0}")]
+    InvalidRequest(ValidationErrors),
+
     /// HTTP request failed
     #[error("HTTP error: {0}")]
     HttpError(#[from] reqwest::Error),
@@ -87,10 +92,22 @@ pub enum AllscreenshotsError {
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
 
+    /// Decoding or encoding an image failed
+    #[error("Image error: {0}")]
+    ImageError(String),
+
+    /// Reading or writing a local file failed
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
     /// Configuration error
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    /// The quota guard refused a request that would exceed the quota or budget
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
     /// Environment variable not set
     #[error("Environment variable '{0}' not set")]
     EnvVarNotSet(String),
@@ -128,6 +145,12 @@ impl AllscreenshotsError {
     }
 }
 
+impl From<std::convert::Infallible> for AllscreenshotsError {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
 /// API error response structure for deserialization.
 #[derive(Debug, serde::Deserialize)]
 pub(crate) struct ApiErrorResponse {
@@ -0,0 +1,698 @@
+//! Offline layout engine for composed images.
+//!
+//! Computes a [`LayoutPreviewResponse`] in the shape returned by
+//! [`AllscreenshotsClient::preview_layout`](crate::AllscreenshotsClient::preview_layout)
+//! without a network round-trip, so previews can be recomputed interactively.
+//! Placements are guaranteed to lie within the canvas and never overlap, but
+//! are an approximation: they are not checked against the API and may differ
+//! from its response.
+
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+
+/// Width of a cell when no thumbnail width is configured.
+pub const DEFAULT_CELL_WIDTH: i32 = 400;
+
+/// Aspect ratio (width / height) assumed for images without one.
+pub const DEFAULT_ASPECT_RATIO: f64 = 16.0 / 9.0;
+
+/// Largest spacing, padding and cell width, in pixels.
+pub const MAX_LAYOUT_LENGTH: i32 = 10_000;
+
+/// Most extreme aspect ratio accepted, either way round.
+pub const MAX_ASPECT_RATIO: f64 = 100.0;
+
+/// Maximum number of images in a layout.
+pub const MAX_LAYOUT_IMAGES: usize = 1000;
+
+/// Largest canvas, in pixels, after scaling to the maximum dimensions.
+pub const MAX_CANVAS_PIXELS: i64 = 1 << 28;
+
+/// Inputs for computing a layout locally.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{LayoutSpec, LayoutType};
+///
+/// let preview = LayoutSpec::new(LayoutType::Grid, 4)
+///     .columns(2)
+///     .spacing(10)
+///     .padding(20)
+///     .compute()
+///     .unwrap();
+///
+/// assert_eq!(preview.placements.len(), 4);
+/// assert_eq!((preview.canvas_width, preview.canvas_height), (850, 500));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutSpec {
+    /// Layout type
+    pub layout: LayoutType,
+    /// Aspect ratio (width / height) of every image
+    pub aspect_ratios: Vec<f64>,
+    /// Number of columns, chosen automatically if unset
+    pub columns: Option<i32>,
+    /// Spacing between images
+    pub spacing: i32,
+    /// Padding around the canvas
+    pub padding: i32,
+    /// Vertical alignment of images within a row
+    pub alignment: Alignment,
+    /// Maximum canvas width
+    pub max_width: Option<i32>,
+    /// Maximum canvas height
+    pub max_height: Option<i32>,
+    /// Width of a single cell before scaling to the maximum dimensions
+    pub cell_width: i32,
+}
+
+impl LayoutSpec {
+    /// Create a spec for `image_count` images with the default aspect ratio.
+    pub fn new(layout: LayoutType, image_count: usize) -> Self {
+        Self {
+            layout,
+            aspect_ratios: vec![DEFAULT_ASPECT_RATIO; image_count],
+            columns: None,
+            spacing: 0,
+            padding: 0,
+            alignment: Alignment::default(),
+            max_width: None,
+            max_height: None,
+            cell_width: DEFAULT_CELL_WIDTH,
+        }
+    }
+
+    /// Create a spec from a compose output configuration.
+    pub fn from_output(output: &ComposeOutputConfig, image_count: usize) -> Self {
+        Self {
+            layout: output.layout.unwrap_or_default(),
+            columns: output.columns,
+            spacing: output.spacing.unwrap_or(0),
+            padding: output.padding.unwrap_or(0),
+            alignment: output.alignment.unwrap_or_default(),
+            max_width: output.max_width,
+            max_height: output.max_height,
+            cell_width: output.thumbnail_width.unwrap_or(DEFAULT_CELL_WIDTH),
+            ..Self::new(output.layout.unwrap_or_default(), image_count)
+        }
+    }
+
+    /// Set the aspect ratio (width / height) of every image.
+    ///
+    /// This also sets the number of images.
+    pub fn aspect_ratios(mut self, aspect_ratios: Vec<f64>) -> Self {
+        self.aspect_ratios = aspect_ratios;
+        self
+    }
+
+    /// Set the number of columns.
+    pub fn columns(mut self, columns: i32) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Set the spacing between images.
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set the padding around the canvas.
+    pub fn padding(mut self, padding: i32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set the vertical alignment of images within a row.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Set the maximum canvas width.
+    pub fn max_width(mut self, max_width: i32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Set the maximum canvas height.
+    pub fn max_height(mut self, max_height: i32) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+
+    /// Set the width of a single cell.
+    pub fn cell_width(mut self, cell_width: i32) -> Self {
+        self.cell_width = cell_width;
+        self
+    }
+
+    /// The layout actually used, resolving [`LayoutType::Auto`].
+    ///
+    /// Auto picks a grid when all images have similar aspect ratios and
+    /// justified rows (partitioning) otherwise.
+    pub fn resolved_layout(&self) -> LayoutType {
+        match self.layout {
+            LayoutType::Auto => {
+                let min = self.aspect_ratios.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = self.aspect_ratios.iter().cloned().fold(0.0, f64::max);
+                if self.aspect_ratios.is_empty() || max / min <= 1.2 {
+                    LayoutType::Grid
+                } else {
+                    LayoutType::Partitioning
+                }
+            }
+            layout => layout,
+        }
+    }
+
+    /// Compute the canvas size and the placement of every image.
+    pub fn compute(&self) -> Result<LayoutPreviewResponse, AllscreenshotsError> {
+        self.validate()?;
+
+        let resolved = self.resolved_layout();
+        let mut layout = match resolved {
+            LayoutType::Horizontal => self.horizontal(),
+            LayoutType::Vertical => self.vertical(),
+            LayoutType::Masonry => self.masonry(),
+            LayoutType::Mondrian => self.mondrian(),
+            LayoutType::Partitioning => self.partitioning(),
+            LayoutType::Grid | LayoutType::Auto => self.grid(),
+        };
+        layout.fit(self.max_width, self.max_height);
+        if i64::from(layout.width) * i64::from(layout.height) > MAX_CANVAS_PIXELS {
+            return Err(AllscreenshotsError::ValidationError(format!(
+                "Canvas of {}x{} pixels is too large; set a maximum width or height",
+                layout.width, layout.height
+            )));
+        }
+
+        Ok(LayoutPreviewResponse {
+            layout: layout_name(self.layout),
+            resolved_layout: Some(layout_name(resolved)),
+            canvas_width: layout.width,
+            canvas_height: layout.height,
+            placements: layout
+                .rects
+                .into_iter()
+                .enumerate()
+                .map(|(index, r)| PlacementPreview {
+                    index: index as i32,
+                    x: r.x,
+                    y: r.y,
+                    width: r.width,
+                    height: r.height,
+                    label: None,
+                })
+                .collect(),
+            metadata: None,
+        })
+    }
+
+    /// The bounds keep every intermediate sum well within `i32`.
+    fn validate(&self) -> Result<(), AllscreenshotsError> {
+        let invalid = |message: String| Err(AllscreenshotsError::ValidationError(message));
+        if self.aspect_ratios.is_empty() {
+            return invalid("A layout needs at least one image".to_string());
+        }
+        if self.aspect_ratios.len() > MAX_LAYOUT_IMAGES {
+            return invalid(format!("A layout can hold at most {} images", MAX_LAYOUT_IMAGES));
+        }
+        let ratios = 1.0 / MAX_ASPECT_RATIO..=MAX_ASPECT_RATIO;
+        if self.aspect_ratios.iter().any(|r| !ratios.contains(r)) {
+            return invalid(format!("Aspect ratios must be between 1:{0} and {0}:1", MAX_ASPECT_RATIO));
+        }
+        if self.columns.is_some_and(|c| c < 1) {
+            return invalid("Columns must be at least 1".to_string());
+        }
+        let lengths = 0..=MAX_LAYOUT_LENGTH;
+        if !lengths.contains(&self.spacing) || !lengths.contains(&self.padding) {
+            return invalid(format!("Spacing and padding must be between 0 and {}", MAX_LAYOUT_LENGTH));
+        }
+        if !(1..=MAX_LAYOUT_LENGTH).contains(&self.cell_width) {
+            return invalid(format!("Cell width must be between 1 and {}", MAX_LAYOUT_LENGTH));
+        }
+        if self.max_width.is_some_and(|w| w < 1) || self.max_height.is_some_and(|h| h < 1) {
+            return invalid("Maximum dimensions must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    fn count(&self) -> i32 {
+        self.aspect_ratios.len() as i32
+    }
+
+    fn column_count(&self) -> i32 {
+        let auto = (self.count() as f64).sqrt().ceil() as i32;
+        self.columns.unwrap_or(auto).clamp(1, self.count())
+    }
+
+    /// Height of a cell holding an image with the default aspect ratio.
+    fn row_height(&self) -> i32 {
+        scaled(self.cell_width, 1.0 / DEFAULT_ASPECT_RATIO)
+    }
+
+    fn image_height(&self, ratio: f64) -> i32 {
+        scaled(self.cell_width, 1.0 / ratio)
+    }
+
+    fn grid(&self) -> Layout {
+        let columns = self.column_count();
+        let (p, s, w) = (self.padding, self.spacing, self.cell_width);
+        let mut rects = Vec::new();
+        let mut y = p;
+        for row in self.aspect_ratios.chunks(columns as usize) {
+            let heights: Vec<i32> = row.iter().map(|r| self.image_height(*r)).collect();
+            let row_height = heights.iter().copied().max().unwrap_or(0);
+            for (i, height) in heights.into_iter().enumerate() {
+                let offset = match self.alignment {
+                    Alignment::Top => 0,
+                    Alignment::Center => (row_height - height) / 2,
+                    Alignment::Bottom => row_height - height,
+                };
+                rects.push(Rect::new(p + i as i32 * (w + s), y + offset, w, height));
+            }
+            y += row_height + s;
+        }
+        Layout {
+            width: 2 * p + columns * w + (columns - 1) * s,
+            height: y - s + p,
+            rects,
+        }
+    }
+
+    fn horizontal(&self) -> Layout {
+        let (p, s, h) = (self.padding, self.spacing, self.row_height());
+        let mut rects = Vec::new();
+        let mut x = p;
+        for ratio in &self.aspect_ratios {
+            let width = scaled(h, *ratio);
+            rects.push(Rect::new(x, p, width, h));
+            x += width + s;
+        }
+        Layout {
+            width: x - s + p,
+            height: 2 * p + h,
+            rects,
+        }
+    }
+
+    fn vertical(&self) -> Layout {
+        let (p, s, w) = (self.padding, self.spacing, self.cell_width);
+        let mut rects = Vec::new();
+        let mut y = p;
+        for ratio in &self.aspect_ratios {
+            let height = self.image_height(*ratio);
+            rects.push(Rect::new(p, y, w, height));
+            y += height + s;
+        }
+        Layout {
+            width: 2 * p + w,
+            height: y - s + p,
+            rects,
+        }
+    }
+
+    fn masonry(&self) -> Layout {
+        let columns = self.column_count();
+        let (p, s, w) = (self.padding, self.spacing, self.cell_width);
+        let mut bottoms = vec![p; columns as usize];
+        let mut rects = Vec::new();
+        for ratio in &self.aspect_ratios {
+            let (column, top) = bottoms
+                .iter()
+                .copied()
+                .enumerate()
+                .min_by_key(|(i, bottom)| (*bottom, *i))
+                .unwrap_or((0, p));
+            let height = self.image_height(*ratio);
+            rects.push(Rect::new(p + column as i32 * (w + s), top, w, height));
+            bottoms[column] = top + height + s;
+        }
+        Layout {
+            width: 2 * p + columns * w + (columns - 1) * s,
+            height: bottoms.into_iter().max().unwrap_or(p) - s + p,
+            rects,
+        }
+    }
+
+    /// Justified rows: images keep their aspect ratio and every full row
+    /// spans the same width.
+    fn partitioning(&self) -> Layout {
+        let columns = self.column_count();
+        let (p, s) = (self.padding, self.spacing);
+        let target = columns * self.cell_width + (columns - 1) * s;
+        let natural = self.row_height();
+
+        let mut rects = Vec::new();
+        let mut y = p;
+        let mut start = 0;
+        while start < self.aspect_ratios.len() {
+            // Grow the row until it is at least as wide as the target at the natural height
+            let mut end = start;
+            let mut ratio_sum = 0.0;
+            while end < self.aspect_ratios.len() {
+                ratio_sum += self.aspect_ratios[end];
+                end += 1;
+                let gaps = (end - start - 1) as i32 * s;
+                if ratio_sum * natural as f64 + gaps as f64 >= target as f64 {
+                    break;
+                }
+            }
+            let row = &self.aspect_ratios[start..end];
+            let gaps = (row.len() as i32 - 1) * s;
+            let full = ratio_sum * natural as f64 + gaps as f64 >= target as f64;
+            let height = if full {
+                (((target - gaps) as f64 / ratio_sum).floor() as i32).max(1)
+            } else {
+                natural
+            };
+
+            let mut x = p;
+            for (i, ratio) in row.iter().enumerate() {
+                let width = if full && i == row.len() - 1 {
+                    // The last image absorbs rounding so the row ends exactly at the target
+                    (p + target - x).max(1)
+                } else {
+                    scaled(height, *ratio)
+                };
+                rects.push(Rect::new(x, y, width, height));
+                x += width + s;
+            }
+            y += height + s;
+            start = end;
+        }
+
+        let right = rects.iter().map(|r| r.x + r.width).max().unwrap_or(p);
+        Layout {
+            width: right.max(p + target) + p,
+            height: y - s + p,
+            rects,
+        }
+    }
+
+    /// Recursive splits filling a fixed canvas; images are cropped to their cells.
+    fn mondrian(&self) -> Layout {
+        let columns = self.column_count();
+        let rows = (self.count() + columns - 1) / columns;
+        let (p, s) = (self.padding, self.spacing);
+        let inner = Rect::new(
+            p,
+            p,
+            columns * self.cell_width + (columns - 1) * s,
+            rows * self.row_height() + (rows - 1) * s,
+        );
+
+        let mut rects = vec![Rect::new(0, 0, 0, 0); self.aspect_ratios.len()];
+        split(inner, 0, self.aspect_ratios.len(), s, &mut rects);
+        Layout {
+            width: inner.width + 2 * p,
+            height: inner.height + 2 * p,
+            rects,
+        }
+    }
+}
+
+/// Divide `area` between images `start..end`, splitting along its longer side.
+fn split(area: Rect, start: usize, end: usize, spacing: i32, rects: &mut [Rect]) {
+    let count = end - start;
+    if count == 1 {
+        rects[start] = area;
+        return;
+    }
+
+    let first = count / 2;
+    let horizontal = area.width >= area.height;
+    let length = if horizontal { area.width } else { area.height };
+    // Drop the gap rather than produce empty cells when the area is too small
+    let gap = if length - spacing >= count as i32 { spacing } else { 0 };
+    let available = length - gap;
+    let a = ((available as i64 * first as i64) / count as i64) as i32;
+    let lo = (first as i32).min(available);
+    let a = a.clamp(lo, (available - (count - first) as i32).max(lo));
+    let b = available - a;
+
+    let (left, right) = if horizontal {
+        (
+            Rect::new(area.x, area.y, a, area.height),
+            Rect::new(area.x + a + gap, area.y, b, area.height),
+        )
+    } else {
+        (
+            Rect::new(area.x, area.y, area.width, a),
+            Rect::new(area.x, area.y + a + gap, area.width, b),
+        )
+    };
+    split(left, start, start + first, spacing, rects);
+    split(right, start + first, end, spacing, rects);
+}
+
+fn scaled(value: i32, factor: f64) -> i32 {
+    ((value as f64 * factor).round() as i32).max(1)
+}
+
+fn layout_name(layout: LayoutType) -> String {
+    serde_json::to_value(layout)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Rect {
+    fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self { x, y, width, height }
+    }
+}
+
+struct Layout {
+    width: i32,
+    height: i32,
+    rects: Vec<Rect>,
+}
+
+impl Layout {
+    /// Scale the layout down uniformly to fit the maximum dimensions.
+    ///
+    /// Edges are floored after scaling, which keeps placements disjoint and
+    /// inside the canvas.
+    fn fit(&mut self, max_width: Option<i32>, max_height: Option<i32>) {
+        let fx = max_width.map(|m| m as f64 / self.width as f64).unwrap_or(1.0);
+        let fy = max_height.map(|m| m as f64 / self.height as f64).unwrap_or(1.0);
+        let factor = fx.min(fy);
+        if factor >= 1.0 {
+            return;
+        }
+
+        let scale = |v: i32| (v as f64 * factor).floor() as i32;
+        for rect in &mut self.rects {
+            let (right, bottom) = (scale(rect.x + rect.width), scale(rect.y + rect.height));
+            rect.x = scale(rect.x);
+            rect.y = scale(rect.y);
+            rect.width = right - rect.x;
+            rect.height = bottom - rect.y;
+        }
+        self.width = scale(self.width);
+        self.height = scale(self.height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use serde::Deserialize;
+
+    const LAYOUTS: [LayoutType; 7] = [
+        LayoutType::Grid,
+        LayoutType::Horizontal,
+        LayoutType::Vertical,
+        LayoutType::Masonry,
+        LayoutType::Mondrian,
+        LayoutType::Partitioning,
+        LayoutType::Auto,
+    ];
+
+    fn overlaps(a: &PlacementPreview, b: &PlacementPreview) -> bool {
+        a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+    }
+
+    fn spec_strategy() -> impl Strategy<Value = LayoutSpec> {
+        (
+            0..LAYOUTS.len(),
+            prop::collection::vec(0.2f64..5.0, 1..=MAX_COMPOSE_CAPTURES),
+            prop::option::of(1..=10i32),
+            0..=100i32,
+            0..=100i32,
+            prop::sample::select(vec![Alignment::Top, Alignment::Center, Alignment::Bottom]),
+            prop::option::of(100..=10000i32),
+            prop::option::of(100..=10000i32),
+            50..=2000i32,
+        )
+            .prop_map(|(layout, ratios, columns, spacing, padding, alignment, max_w, max_h, cell)| LayoutSpec {
+                layout: LAYOUTS[layout],
+                aspect_ratios: ratios,
+                columns,
+                spacing,
+                padding,
+                alignment,
+                max_width: max_w,
+                max_height: max_h,
+                cell_width: cell,
+            })
+    }
+
+    /// The preview, or `None` if the canvas is legitimately too large to render.
+    fn computed(spec: &LayoutSpec) -> Option<LayoutPreviewResponse> {
+        match spec.compute() {
+            Err(AllscreenshotsError::ValidationError(message)) if message.starts_with("Canvas") => None,
+            result => Some(result.unwrap()),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_placements_in_bounds_and_disjoint(spec in spec_strategy()) {
+            let Some(preview) = computed(&spec) else { return Ok(()) };
+            prop_assert_eq!(preview.placements.len(), spec.aspect_ratios.len());
+            if let Some(max) = spec.max_width {
+                prop_assert!(preview.canvas_width <= max);
+            }
+            if let Some(max) = spec.max_height {
+                prop_assert!(preview.canvas_height <= max);
+            }
+            for (i, a) in preview.placements.iter().enumerate() {
+                prop_assert!(a.x >= 0 && a.y >= 0 && a.width >= 0 && a.height >= 0, "{:?}", a);
+                prop_assert!(a.x + a.width <= preview.canvas_width, "{:?} exceeds width {}", a, preview.canvas_width);
+                prop_assert!(a.y + a.height <= preview.canvas_height, "{:?} exceeds height {}", a, preview.canvas_height);
+                for b in &preview.placements[i + 1..] {
+                    prop_assert!(!overlaps(a, b), "{:?} overlaps {:?}", a, b);
+                }
+            }
+        }
+
+        #[test]
+        fn prop_unscaled_placements_are_visible(spec in spec_strategy()) {
+            let spec = LayoutSpec { max_width: None, max_height: None, ..spec };
+            let Some(preview) = computed(&spec) else { return Ok(()) };
+            for placement in &preview.placements {
+                prop_assert!(placement.width >= 1 && placement.height >= 1, "{:?}", placement);
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Fixture {
+        layout: LayoutType,
+        aspect_ratios: Vec<f64>,
+        columns: Option<i32>,
+        #[serde(default)]
+        spacing: i32,
+        #[serde(default)]
+        padding: i32,
+        thumbnail_width: Option<i32>,
+        max_width: Option<i32>,
+        max_height: Option<i32>,
+        response: LayoutPreviewResponse,
+    }
+
+    /// Regression check against the hand-derived fixtures, allowing 1px of rounding difference.
+    #[test]
+    fn test_matches_preview_fixtures() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/layout");
+        let mut checked = 0;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let fixture: Fixture = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+            let spec = LayoutSpec {
+                columns: fixture.columns,
+                spacing: fixture.spacing,
+                padding: fixture.padding,
+                max_width: fixture.max_width,
+                max_height: fixture.max_height,
+                cell_width: fixture.thumbnail_width.unwrap_or(DEFAULT_CELL_WIDTH),
+                ..LayoutSpec::new(fixture.layout, 0).aspect_ratios(fixture.aspect_ratios)
+            };
+            let actual = spec.compute().unwrap();
+            let expected = fixture.response;
+
+            let close = |a: i32, b: i32| (a - b).abs() <= 1;
+            let name = path.display();
+            assert!(close(actual.canvas_width, expected.canvas_width), "{}: canvas width {}", name, actual.canvas_width);
+            assert!(close(actual.canvas_height, expected.canvas_height), "{}: canvas height {}", name, actual.canvas_height);
+            assert_eq!(actual.placements.len(), expected.placements.len(), "{}", name);
+            for (a, e) in actual.placements.iter().zip(&expected.placements) {
+                assert!(
+                    close(a.x, e.x) && close(a.y, e.y) && close(a.width, e.width) && close(a.height, e.height),
+                    "{}: placement {:?} differs from {:?}",
+                    name,
+                    a,
+                    e
+                );
+            }
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_from_output_config() {
+        let output = ComposeOutputConfig {
+            layout: Some(LayoutType::Vertical),
+            spacing: Some(8),
+            thumbnail_width: Some(200),
+            ..Default::default()
+        };
+        let preview = LayoutSpec::from_output(&output, 2).compute().unwrap();
+        assert_eq!(preview.layout, "VERTICAL");
+        assert_eq!((preview.canvas_width, preview.canvas_height), (200, 113 + 8 + 113));
+    }
+
+    #[test]
+    fn test_auto_resolves_by_aspect_ratio() {
+        let similar = LayoutSpec::new(LayoutType::Auto, 3).compute().unwrap();
+        assert_eq!(similar.resolved_layout.as_deref(), Some("GRID"));
+
+        let mixed = LayoutSpec::new(LayoutType::Auto, 0).aspect_ratios(vec![0.5, 1.78, 3.0]).compute().unwrap();
+        assert_eq!(mixed.resolved_layout.as_deref(), Some("PARTITIONING"));
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        assert!(LayoutSpec::new(LayoutType::Grid, 0).compute().is_err());
+        assert!(LayoutSpec::new(LayoutType::Grid, 2).columns(0).compute().is_err());
+        assert!(LayoutSpec::new(LayoutType::Grid, 0).aspect_ratios(vec![1.0, -1.0]).compute().is_err());
+    }
+
+    #[test]
+    fn test_oversized_specs_are_rejected() {
+        let invalid = |spec: LayoutSpec| matches!(spec.compute(), Err(AllscreenshotsError::ValidationError(_)));
+        for layout in LAYOUTS {
+            assert!(invalid(LayoutSpec::new(layout, 3).spacing(i32::MAX / 2)));
+            assert!(invalid(LayoutSpec::new(layout, 3).padding(i32::MAX)));
+            assert!(invalid(LayoutSpec::new(layout, 3).cell_width(i32::MAX)));
+            assert!(invalid(LayoutSpec::new(layout, 0).aspect_ratios(vec![1e-9, 1.0])));
+            assert!(invalid(LayoutSpec::new(layout, MAX_LAYOUT_IMAGES + 1)));
+        }
+
+        // Within the bounds, but far too tall to render without a maximum height
+        let tall = LayoutSpec::new(LayoutType::Vertical, 0)
+            .aspect_ratios(vec![1.0 / MAX_ASPECT_RATIO; 20])
+            .cell_width(MAX_LAYOUT_LENGTH);
+        assert!(invalid(tall.clone()));
+        let preview = tall.max_height(10_000).compute().unwrap();
+        assert!(preview.canvas_height <= 10_000);
+    }
+}
@@ -40,11 +40,34 @@
 //! # Ok::<(), allscreenshots_sdk::AllscreenshotsError>(())
 //! ```
 
+pub mod bulk;
 pub mod client;
+pub mod compose;
+pub mod config;
 pub mod error;
+pub mod layout;
 pub mod models;
+pub mod quota;
 mod retry;
+pub mod schedules;
+pub mod validation;
 
+pub use bulk::{
+    BatchChunk, BatchJobRecord, BatchProgress, BatchSubmission, BulkBatch, BulkCheckpoint, BulkJobResult,
+    BulkProgress, BulkResultsOptions, ProgressCallback, ScreenshotManyOptions,
+};
 pub use client::{AllscreenshotsClient, AllscreenshotsClientBuilder};
+pub use compose::{ComposeOutput, ComposeProgress, ComposeProgressCallback, ComposeWaitOptions};
+#[cfg(feature = "compose-local")]
+pub use compose::{LocalCompositor, LocalImage};
 pub use error::{AllscreenshotsError, ErrorCode};
+pub use layout::LayoutSpec;
 pub use models::*;
+pub use quota::{
+    QuotaEvent, QuotaEventCallback, QuotaGuard, QuotaMonitor, QuotaMonitorOptions, QuotaResource,
+    DEFAULT_QUOTA_THRESHOLDS,
+};
+pub use schedules::{
+    ConflictPolicy, ImportReport, ScheduleExport, ScheduleHealth, HEALTH_HISTORY_LIMIT, SCHEDULE_EXPORT_VERSION,
+};
+pub use validation::{FieldViolation, Validate, ValidationErrors};
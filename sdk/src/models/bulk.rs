@@ -1,10 +1,32 @@
 //! Bulk screenshot request and response models.
 
+use crate::error::{AllscreenshotsError, ErrorCode};
+use crate::validation::*;
 use serde::{Deserialize, Serialize};
 use super::common::*;
 
+/// Maximum number of URLs accepted in a single bulk request.
+pub const MAX_BULK_URLS: usize = 100;
+
 /// Request for bulk screenshots.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{BulkRequest, BulkDefaults};
+///
+/// let request = BulkRequest::builder()
+///     .url("https://github.com")
+///     .url("https://rust-lang.org")
+///     .defaults(BulkDefaults {
+///         device: Some("Desktop HD".to_string()),
+///         ..Default::default()
+///     })
+///     .webhook_url("https://example.com/webhook")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BulkRequest {
     /// List of URLs to capture (max 100)
@@ -21,6 +43,11 @@ pub struct BulkRequest {
 }
 
 impl BulkRequest {
+    /// Create a new builder for BulkRequest.
+    pub fn builder() -> BulkRequestBuilder {
+        BulkRequestBuilder::default()
+    }
+
     /// Create a new bulk request with the given URLs.
     pub fn new(urls: Vec<BulkUrlRequest>) -> Self {
         Self {
@@ -45,6 +72,77 @@ impl BulkRequest {
     }
 }
 
+/// Builder for BulkRequest.
+#[derive(Debug, Default)]
+pub struct BulkRequestBuilder {
+    request: BulkRequest,
+}
+
+impl BulkRequestBuilder {
+    /// Add a URL to capture with the default options.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.request.urls.push(BulkUrlRequest::new(url));
+        self
+    }
+
+    /// Add a URL to capture with URL-specific options.
+    pub fn url_with_options(mut self, url: impl Into<String>, options: BulkUrlOptions) -> Self {
+        self.request.urls.push(BulkUrlRequest::with_options(url, options));
+        self
+    }
+
+    /// Add several URLs to capture with the default options.
+    pub fn urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.request.urls.extend(urls.into_iter().map(BulkUrlRequest::new));
+        self
+    }
+
+    /// Add a preconfigured URL request.
+    pub fn url_request(mut self, url_request: BulkUrlRequest) -> Self {
+        self.request.urls.push(url_request);
+        self
+    }
+
+    /// Set default options for all URLs.
+    pub fn defaults(mut self, defaults: BulkDefaults) -> Self {
+        self.request.defaults = Some(defaults);
+        self
+    }
+
+    /// Set the webhook URL.
+    pub fn webhook_url(mut self, url: impl Into<String>) -> Self {
+        self.request.webhook_url = Some(url.into());
+        self
+    }
+
+    /// Set the webhook secret.
+    pub fn webhook_secret(mut self, secret: impl Into<String>) -> Self {
+        self.request.webhook_secret = Some(secret.into());
+        self
+    }
+
+    /// Build the request, validating all fields.
+    pub fn build(self) -> Result<BulkRequest, AllscreenshotsError> {
+        self.request.validate()?;
+        Ok(self.request)
+    }
+}
+
+impl Validate for BulkRequest {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_list_len(errors, path, "urls", &self.urls, MAX_BULK_URLS);
+        for (i, url) in self.urls.iter().enumerate() {
+            url.collect_violations(&index_path(path, "urls", i), errors);
+        }
+        self.defaults.collect_violations(&field_path(path, "defaults"), errors);
+        check_optional_http_url(errors, path, "webhookUrl", self.webhook_url.as_deref());
+    }
+}
+
 /// URL configuration for bulk requests.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -74,6 +172,13 @@ impl BulkUrlRequest {
     }
 }
 
+impl Validate for BulkUrlRequest {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_http_url(errors, path, "url", &self.url);
+        self.options.collect_violations(&field_path(path, "options"), errors);
+    }
+}
+
 /// URL-specific options for bulk requests.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -122,6 +227,15 @@ pub struct BulkUrlOptions {
     pub block_level: Option<BlockLevel>,
 }
 
+impl Validate for BulkUrlOptions {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        self.viewport.collect_violations(&field_path(path, "viewport"), errors);
+        check_range(errors, path, "quality", self.quality, 1..=100);
+        check_range(errors, path, "delay", self.delay, 0..=30000);
+        check_range(errors, path, "timeout", self.timeout, 1000..=60000);
+    }
+}
+
 /// Default options for bulk requests.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -170,6 +284,41 @@ pub struct BulkDefaults {
     pub block_level: Option<BlockLevel>,
 }
 
+impl BulkDefaults {
+    /// Fill options left unset here from `defaults`.
+    ///
+    /// A device preset implies a viewport, so `device` and `viewport` are
+    /// taken from `defaults` together, and only if neither is set here.
+    pub fn fill_unset_from(mut self, defaults: &BulkDefaults) -> Self {
+        if self.device.is_none() && self.viewport.is_none() {
+            self.device = defaults.device.clone();
+            self.viewport = defaults.viewport.clone();
+        }
+        self.format = self.format.or_else(|| defaults.format.clone());
+        self.full_page = self.full_page.or(defaults.full_page);
+        self.quality = self.quality.or(defaults.quality);
+        self.delay = self.delay.or(defaults.delay);
+        self.wait_for = self.wait_for.or_else(|| defaults.wait_for.clone());
+        self.wait_until = self.wait_until.or_else(|| defaults.wait_until.clone());
+        self.timeout = self.timeout.or(defaults.timeout);
+        self.dark_mode = self.dark_mode.or(defaults.dark_mode);
+        self.custom_css = self.custom_css.or_else(|| defaults.custom_css.clone());
+        self.block_ads = self.block_ads.or(defaults.block_ads);
+        self.block_cookie_banners = self.block_cookie_banners.or(defaults.block_cookie_banners);
+        self.block_level = self.block_level.or_else(|| defaults.block_level.clone());
+        self
+    }
+}
+
+impl Validate for BulkDefaults {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        self.viewport.collect_violations(&field_path(path, "viewport"), errors);
+        check_range(errors, path, "quality", self.quality, 1..=100);
+        check_range(errors, path, "delay", self.delay, 0..=30000);
+        check_range(errors, path, "timeout", self.timeout, 1000..=60000);
+    }
+}
+
 /// Response for bulk job creation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -254,6 +403,116 @@ pub struct BulkStatusResponse {
     pub completed_at: Option<String>,
 }
 
+impl BulkStatusResponse {
+    /// Returns `true` once every job in the bulk request has finished.
+    pub fn is_finished(&self) -> bool {
+        let terminal = self
+            .status
+            .parse::<JobStatus>()
+            .map(|s| s.is_terminal())
+            .unwrap_or(false);
+        terminal || (self.total_jobs > 0 && self.completed_jobs + self.failed_jobs >= self.total_jobs)
+    }
+
+    /// Failed entries accepted by `filter`, paired with the URL request to resubmit.
+    ///
+    /// Each entry is matched by URL against `original` (the request the bulk
+    /// job was created from) so its per-URL options are kept. Entries that
+    /// cannot be matched are resubmitted with the plain URL.
+    pub fn failed_entries(
+        &self,
+        original: Option<&BulkRequest>,
+        filter: &RetryFilter,
+    ) -> Vec<(&BulkJobDetailInfo, BulkUrlRequest)> {
+        let mut originals: Vec<Option<&BulkUrlRequest>> = original
+            .map(|r| r.urls.iter().map(Some).collect())
+            .unwrap_or_default();
+
+        let mut failed = Vec::new();
+        for job in self.jobs.iter().flatten() {
+            // Consume matches in order so duplicate URLs map to distinct entries
+            let matched = originals
+                .iter_mut()
+                .find(|o| o.map(|u| u.url == job.url).unwrap_or(false))
+                .and_then(Option::take);
+            if job.job_status() == Some(JobStatus::Failed) && filter.matches(job) {
+                failed.push((job, matched.cloned().unwrap_or_else(|| BulkUrlRequest::new(&job.url))));
+            }
+        }
+        failed
+    }
+
+    /// Build a request resubmitting the failed entries accepted by `filter`.
+    ///
+    /// Defaults and webhook settings are copied from `original`. Returns
+    /// `None` if no entry needs to be retried.
+    pub fn retry_request(&self, original: Option<&BulkRequest>, filter: &RetryFilter) -> Option<BulkRequest> {
+        let urls: Vec<_> = self
+            .failed_entries(original, filter)
+            .into_iter()
+            .map(|(_, url)| url)
+            .collect();
+        if urls.is_empty() {
+            return None;
+        }
+
+        let mut request = BulkRequest::new(urls);
+        if let Some(original) = original {
+            request.defaults = original.defaults.clone();
+            request.webhook_url = original.webhook_url.clone();
+            request.webhook_secret = original.webhook_secret.clone();
+        }
+        Some(request)
+    }
+}
+
+/// Selects which failed bulk entries to retry, by error code.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{ErrorCode, RetryFilter};
+///
+/// // Retry timeouts, but not pages that do not exist
+/// let filter = RetryFilter::only([ErrorCode::Timeout]);
+/// let filter = RetryFilter::except([ErrorCode::NotFound]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RetryFilter {
+    /// Retry every failed entry
+    #[default]
+    All,
+    /// Retry only entries that failed with one of these codes
+    ErrorCodes(Vec<ErrorCode>),
+    /// Retry every failed entry except those that failed with one of these codes
+    ExceptErrorCodes(Vec<ErrorCode>),
+}
+
+impl RetryFilter {
+    /// Retry only entries that failed with one of the given codes.
+    pub fn only(codes: impl IntoIterator<Item = ErrorCode>) -> Self {
+        RetryFilter::ErrorCodes(codes.into_iter().collect())
+    }
+
+    /// Retry every failed entry except those that failed with one of the given codes.
+    pub fn except(codes: impl IntoIterator<Item = ErrorCode>) -> Self {
+        RetryFilter::ExceptErrorCodes(codes.into_iter().collect())
+    }
+
+    /// Returns `true` if the entry's error code is accepted by the filter.
+    ///
+    /// Entries without an error code only match [`RetryFilter::All`] and
+    /// [`RetryFilter::ExceptErrorCodes`].
+    pub fn matches(&self, job: &BulkJobDetailInfo) -> bool {
+        let code = job.error_code.as_deref().map(ErrorCode::from);
+        match self {
+            RetryFilter::All => true,
+            RetryFilter::ErrorCodes(codes) => code.map(|c| codes.contains(&c)).unwrap_or(false),
+            RetryFilter::ExceptErrorCodes(codes) => code.map(|c| !codes.contains(&c)).unwrap_or(true),
+        }
+    }
+}
+
 /// Detailed job information for bulk status.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -287,3 +546,176 @@ pub struct BulkJobDetailInfo {
     /// Completion timestamp
     pub completed_at: Option<String>,
 }
+
+impl BulkJobDetailInfo {
+    /// Parse the job status, if it is a known value.
+    pub fn job_status(&self) -> Option<JobStatus> {
+        self.status.parse().ok()
+    }
+
+    /// Returns `true` if the job is in a terminal state.
+    pub fn is_terminal(&self) -> bool {
+        self.job_status().map(|s| s.is_terminal()).unwrap_or(false)
+    }
+
+    /// Returns `true` if the job completed successfully.
+    pub fn is_success(&self) -> bool {
+        self.job_status().map(|s| s.is_success()).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_valid_request() {
+        let request = BulkRequest::new(vec![
+            BulkUrlRequest::new("https://example.com"),
+            BulkUrlRequest::with_options(
+                "https://example.org",
+                BulkUrlOptions {
+                    quality: Some(80),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder() {
+        let request = BulkRequest::builder()
+            .url("https://example.com")
+            .urls(["https://example.org", "https://example.net"])
+            .url_with_options(
+                "https://example.com/mobile",
+                BulkUrlOptions {
+                    device: Some("iPhone 14".to_string()),
+                    ..Default::default()
+                },
+            )
+            .webhook_url("https://example.com/webhook")
+            .webhook_secret("secret")
+            .build()
+            .unwrap();
+
+        assert_eq!(request.urls.len(), 4);
+        assert_eq!(request.urls[3].options.as_ref().unwrap().device.as_deref(), Some("iPhone 14"));
+        assert_eq!(request.webhook_url.as_deref(), Some("https://example.com/webhook"));
+        assert_eq!(request.webhook_secret.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn test_builder_validates() {
+        assert!(BulkRequest::builder().build().is_err());
+        assert!(BulkRequest::builder().url("example.com").build().is_err());
+    }
+
+    #[test]
+    fn test_validate_too_many_urls() {
+        let urls = (0..=MAX_BULK_URLS)
+            .map(|i| BulkUrlRequest::new(format!("https://example.com/{}", i)))
+            .collect();
+        let errors = BulkRequest::new(urls).violations();
+        assert!(errors.has_field("urls"));
+    }
+
+    #[test]
+    fn test_validate_empty_urls() {
+        let errors = BulkRequest::new(vec![]).violations();
+        assert!(errors.has_field("urls"));
+    }
+
+    #[test]
+    fn test_validate_nested_paths() {
+        let request = BulkRequest::new(vec![
+            BulkUrlRequest::new("https://example.com"),
+            BulkUrlRequest::with_options(
+                "example.org",
+                BulkUrlOptions {
+                    quality: Some(0),
+                    viewport: Some(ViewportConfig::new(1920, 1080).with_scale_factor(4)),
+                    ..Default::default()
+                },
+            ),
+        ])
+        .with_defaults(BulkDefaults {
+            timeout: Some(10),
+            ..Default::default()
+        })
+        .with_webhook("not-a-url".to_string(), None);
+
+        let errors = request.violations();
+        assert_eq!(errors.len(), 5);
+        assert!(errors.has_field("urls[1].url"));
+        assert!(errors.has_field("urls[1].options.quality"));
+        assert!(errors.has_field("urls[1].options.viewport.deviceScaleFactor"));
+        assert!(errors.has_field("defaults.timeout"));
+        assert!(errors.has_field("webhookUrl"));
+    }
+
+    fn failed_status() -> BulkStatusResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "bulk-1",
+            "status": "COMPLETED",
+            "totalJobs": 4,
+            "completedJobs": 1,
+            "failedJobs": 3,
+            "progress": 100,
+            "jobs": [
+                {"id": "j0", "url": "https://example.com/a", "status": "COMPLETED"},
+                {"id": "j1", "url": "https://example.com/b", "status": "FAILED", "errorCode": "TIMEOUT"},
+                {"id": "j2", "url": "https://example.com/c", "status": "FAILED", "errorCode": "NOT_FOUND"},
+                {"id": "j3", "url": "https://example.com/b", "status": "FAILED", "errorCode": "TIMEOUT"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_retry_request_keeps_original_options() {
+        let original = BulkRequest::new(vec![
+            BulkUrlRequest::new("https://example.com/a"),
+            BulkUrlRequest::with_options(
+                "https://example.com/b",
+                BulkUrlOptions {
+                    full_page: Some(true),
+                    ..Default::default()
+                },
+            ),
+            BulkUrlRequest::new("https://example.com/c"),
+            BulkUrlRequest::with_options(
+                "https://example.com/b",
+                BulkUrlOptions {
+                    device: Some("iPhone 14".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ])
+        .with_webhook("https://example.com/webhook".to_string(), None);
+
+        let retry = failed_status()
+            .retry_request(Some(&original), &RetryFilter::only([ErrorCode::Timeout]))
+            .unwrap();
+        assert_eq!(retry.urls.len(), 2);
+        assert_eq!(retry.urls[0].options.as_ref().unwrap().full_page, Some(true));
+        assert_eq!(retry.urls[1].options.as_ref().unwrap().device.as_deref(), Some("iPhone 14"));
+        assert_eq!(retry.webhook_url.as_deref(), Some("https://example.com/webhook"));
+    }
+
+    #[test]
+    fn test_retry_filter() {
+        let status = failed_status();
+        assert_eq!(status.failed_entries(None, &RetryFilter::All).len(), 3);
+
+        let except = status.retry_request(None, &RetryFilter::except([ErrorCode::Timeout])).unwrap();
+        assert_eq!(except.urls.len(), 1);
+        assert_eq!(except.urls[0].url, "https://example.com/c");
+        assert!(except.urls[0].options.is_none());
+
+        assert!(status
+            .retry_request(None, &RetryFilter::only([ErrorCode::RateLimitExceeded]))
+            .is_none());
+    }
+}
@@ -0,0 +1,198 @@
+//! Colors for composed images.
+
+use crate::error::AllscreenshotsError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Named colors accepted by [`Color::parse`] (the CSS basic color keywords).
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("black", [0x00, 0x00, 0x00]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("aqua", [0x00, 0xff, 0xff]),
+];
+
+/// An RGBA color for compose backgrounds, labels, borders and shadows.
+///
+/// Parses `#RRGGBB`, `#RRGGBBAA`, the `#RGB` shorthand, `transparent` and the
+/// CSS basic color names (`white`, `navy`, ...). Serializes in the API's
+/// format: `#RRGGBB` for opaque colors, `#RRGGBBAA` otherwise, and
+/// `transparent` for fully transparent black.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::Color;
+///
+/// let color: Color = "navy".parse().unwrap();
+/// assert_eq!(color.to_string(), "#000080");
+/// assert_eq!(Color::rgba(0, 0, 0, 0x40).to_string(), "#00000040");
+/// assert!(Color::parse("#12345").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+    /// Red channel
+    pub r: u8,
+    /// Green channel
+    pub g: u8,
+    /// Blue channel
+    pub b: u8,
+    /// Alpha channel (255 is opaque)
+    pub a: u8,
+}
+
+impl Color {
+    /// Fully transparent.
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+    /// Opaque white.
+    pub const WHITE: Color = Color::rgb(0xff, 0xff, 0xff);
+    /// Opaque black.
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+
+    /// Create an opaque color.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 0xff }
+    }
+
+    /// Create a color with an alpha channel.
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Parse a hex or named color.
+    pub fn parse(value: &str) -> Result<Self, AllscreenshotsError> {
+        let invalid = || {
+            AllscreenshotsError::ValidationError(format!(
+                "Invalid color '{}': expected #RRGGBB, #RRGGBBAA, \"transparent\" or a color name",
+                value
+            ))
+        };
+
+        if value.eq_ignore_ascii_case("transparent") {
+            return Ok(Self::TRANSPARENT);
+        }
+        if let Some((_, [r, g, b])) = NAMED_COLORS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(value))
+        {
+            return Ok(Self::rgb(*r, *g, *b));
+        }
+
+        let hex = value.strip_prefix('#').ok_or_else(invalid)?;
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+        match hex.len() {
+            3 => {
+                let short = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).map(|v| v * 0x11).map_err(|_| invalid());
+                Ok(Self::rgb(short(0)?, short(1)?, short(2)?))
+            }
+            6 => Ok(Self::rgb(channel(0)?, channel(2)?, channel(4)?)),
+            8 => Ok(Self::rgba(channel(0)?, channel(2)?, channel(4)?, channel(6)?)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Returns `true` if the color is fully opaque.
+    pub fn is_opaque(&self) -> bool {
+        self.a == 0xff
+    }
+}
+
+impl FromStr for Color {
+    type Err = AllscreenshotsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = AllscreenshotsError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl TryFrom<&str> for Color {
+    type Error = AllscreenshotsError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        color.to_string()
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Self::TRANSPARENT {
+            write!(f, "transparent")
+        } else if self.is_opaque() {
+            write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            write!(f, "#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(Color::parse("#ff8000").unwrap(), Color::rgb(0xff, 0x80, 0x00));
+        assert_eq!(Color::parse("#FF800080").unwrap(), Color::rgba(0xff, 0x80, 0x00, 0x80));
+        assert_eq!(Color::parse("#f80").unwrap(), Color::rgb(0xff, 0x88, 0x00));
+    }
+
+    #[test]
+    fn test_parse_named() {
+        assert_eq!(Color::parse("transparent").unwrap(), Color::TRANSPARENT);
+        assert_eq!(Color::parse("White").unwrap(), Color::WHITE);
+        assert_eq!(Color::parse("grey").unwrap(), Color::parse("gray").unwrap());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for value in ["", "#", "ffffff", "#12345", "#1234567", "#gggggg", "#ff80é0", "rebeccapurple"] {
+            assert!(Color::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_display_uses_api_format() {
+        assert_eq!(Color::WHITE.to_string(), "#ffffff");
+        assert_eq!(Color::rgba(1, 2, 3, 4).to_string(), "#01020304");
+        assert_eq!(Color::TRANSPARENT.to_string(), "transparent");
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(serde_json::to_string(&Color::BLACK).unwrap(), "\"#000000\"");
+        assert_eq!(serde_json::from_str::<Color>("\"#00000040\"").unwrap(), Color::rgba(0, 0, 0, 0x40));
+        assert!(serde_json::from_str::<Color>("\"nope\"").is_err());
+    }
+}
@@ -1,9 +1,16 @@
 //! Common types used across multiple API endpoints.
 
+use crate::error::AllscreenshotsError;
+use crate::validation::{check_range, ValidationErrors, Validate};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Fields returned by the API that this SDK does not know about yet, kept so
+/// they survive a round trip.
+pub type ExtraFields = BTreeMap<String, serde_json::Value>;
 
 /// Viewport configuration for screenshots.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ViewportConfig {
     /// Width in pixels (100-4096)
@@ -34,9 +41,49 @@ impl ViewportConfig {
     }
 }
 
+impl Validate for ViewportConfig {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_range(errors, path, "width", self.width, 100..=4096);
+        check_range(errors, path, "height", self.height, 100..=4096);
+        check_range(errors, path, "deviceScaleFactor", self.device_scale_factor, 1..=3);
+    }
+}
+
+/// Implement `as_str`, `Serialize` and `Deserialize` for an enum of API
+/// values with an `Unknown(String)` fallback, so values added to the API
+/// later are kept as they are.
+macro_rules! api_value_enum {
+    ($name:ident { $($variant:ident => $value:literal),+ $(,)? }) => {
+        impl $name {
+            /// The value the API uses for this variant.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok(match value.as_str() {
+                    $($value => Self::$variant,)+
+                    _ => Self::Unknown(value),
+                })
+            }
+        }
+    };
+}
+
 /// Image format for screenshots.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ImageFormat {
     /// PNG format (lossless)
     #[default]
@@ -49,28 +96,37 @@ pub enum ImageFormat {
     Webp,
     /// PDF format
     Pdf,
+    /// A format this SDK does not know, kept as returned by the API
+    Unknown(String),
 }
 
+api_value_enum!(ImageFormat { Png => "png", Jpeg => "jpeg", Jpg => "jpg", Webp => "webp", Pdf => "pdf" });
+
 /// Wait condition for page loading.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum WaitUntil {
     /// Wait for the load event
     #[default]
     Load,
     /// Wait for DOMContentLoaded
-    #[serde(rename = "domcontentloaded")]
     DomContentLoaded,
     /// Wait for network to be idle
-    #[serde(rename = "networkidle")]
     NetworkIdle,
     /// Wait for first commit
     Commit,
+    /// A condition this SDK does not know, kept as returned by the API
+    Unknown(String),
 }
 
+api_value_enum!(WaitUntil {
+    Load => "load",
+    DomContentLoaded => "domcontentloaded",
+    NetworkIdle => "networkidle",
+    Commit => "commit",
+});
+
 /// Block level for ads and trackers.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum BlockLevel {
     /// No blocking
     #[default]
@@ -85,8 +141,19 @@ pub enum BlockLevel {
     ProPlus,
     /// Ultimate blocking
     Ultimate,
+    /// A level this SDK does not know, kept as returned by the API
+    Unknown(String),
 }
 
+api_value_enum!(BlockLevel {
+    None => "none",
+    Light => "light",
+    Normal => "normal",
+    Pro => "pro",
+    ProPlus => "pro_plus",
+    Ultimate => "ultimate",
+});
+
 /// Job status for async operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -103,6 +170,21 @@ pub enum JobStatus {
     Cancelled,
 }
 
+impl std::str::FromStr for JobStatus {
+    type Err = AllscreenshotsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "QUEUED" => Ok(JobStatus::Queued),
+            "PROCESSING" => Ok(JobStatus::Processing),
+            "COMPLETED" => Ok(JobStatus::Completed),
+            "FAILED" => Ok(JobStatus::Failed),
+            "CANCELLED" => Ok(JobStatus::Cancelled),
+            _ => Err(AllscreenshotsError::ValidationError(format!("Unknown job status '{}'", s))),
+        }
+    }
+}
+
 impl JobStatus {
     /// Returns `true` if the job is in a terminal state.
     pub fn is_terminal(&self) -> bool {
@@ -1,8 +1,14 @@
 //! Compose (multi-screenshot layout) request and response models.
 
+use crate::error::AllscreenshotsError;
+use crate::validation::*;
 use serde::{Deserialize, Serialize};
+use super::color::Color;
 use super::common::*;
 
+/// Maximum number of captures or variants in a single compose request.
+pub const MAX_COMPOSE_CAPTURES: usize = 20;
+
 /// Layout type for composed images.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -37,8 +43,41 @@ pub enum Alignment {
     Bottom,
 }
 
+/// Position of a label relative to its screenshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelPosition {
+    /// Above the screenshot
+    Top,
+    /// Below the screenshot
+    #[default]
+    Bottom,
+}
+
 /// Request to compose multiple screenshots.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{CaptureItem, ComposeOutputConfig, ComposeRequest, LayoutType};
+///
+/// let request = ComposeRequest::builder()
+///     .capture(CaptureItem::new("https://github.com").with_device("Desktop HD"))
+///     .capture(CaptureItem::new("https://github.com").with_device("iPhone 14"))
+///     .output(
+///         ComposeOutputConfig::builder()
+///             .layout(LayoutType::Horizontal)
+///             .spacing(20)
+///             .build()
+///             .unwrap(),
+///     )
+///     .webhook_url("https://example.com/webhook")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(request.captures_mode, Some(true));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ComposeRequest {
     /// List of URLs/captures to include (max 20)
@@ -74,35 +113,27 @@ pub struct ComposeRequest {
 }
 
 impl ComposeRequest {
+    /// Create a new builder for ComposeRequest.
+    pub fn builder() -> ComposeRequestBuilder {
+        ComposeRequestBuilder::default()
+    }
+
     /// Create a compose request with multiple captures.
     pub fn with_captures(captures: Vec<CaptureItem>) -> Self {
         Self {
             captures: Some(captures),
-            url: None,
-            variants: None,
-            defaults: None,
-            output: None,
-            is_async: None,
-            webhook_url: None,
-            webhook_secret: None,
-            captures_mode: None,
-            variants_mode: None,
+            captures_mode: Some(true),
+            ..Default::default()
         }
     }
 
     /// Create a compose request with variants of a single URL.
     pub fn with_variants(url: String, variants: Vec<VariantConfig>) -> Self {
         Self {
-            captures: None,
             url: Some(url),
             variants: Some(variants),
-            defaults: None,
-            output: None,
-            is_async: None,
-            webhook_url: None,
-            webhook_secret: None,
-            captures_mode: None,
-            variants_mode: None,
+            variants_mode: Some(true),
+            ..Default::default()
         }
     }
 
@@ -123,6 +154,136 @@ impl ComposeRequest {
         self.is_async = Some(is_async);
         self
     }
+
+    /// Set the webhook URL.
+    pub fn with_webhook(mut self, url: String, secret: Option<String>) -> Self {
+        self.webhook_url = Some(url);
+        self.webhook_secret = secret;
+        self
+    }
+}
+
+/// Builder for ComposeRequest.
+///
+/// Add either captures (different URLs) or a URL with variants; the
+/// corresponding mode flag is set when the request is built.
+#[derive(Debug, Default)]
+pub struct ComposeRequestBuilder {
+    request: ComposeRequest,
+}
+
+impl ComposeRequestBuilder {
+    /// Add a capture.
+    pub fn capture(mut self, capture: CaptureItem) -> Self {
+        self.request.captures.get_or_insert_with(Vec::new).push(capture);
+        self
+    }
+
+    /// Add several captures.
+    pub fn captures(mut self, captures: impl IntoIterator<Item = CaptureItem>) -> Self {
+        self.request.captures.get_or_insert_with(Vec::new).extend(captures);
+        self
+    }
+
+    /// Set the URL to capture in variants mode.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.request.url = Some(url.into());
+        self
+    }
+
+    /// Add a variant of the URL.
+    pub fn variant(mut self, variant: VariantConfig) -> Self {
+        self.request.variants.get_or_insert_with(Vec::new).push(variant);
+        self
+    }
+
+    /// Add several variants of the URL.
+    pub fn variants(mut self, variants: impl IntoIterator<Item = VariantConfig>) -> Self {
+        self.request.variants.get_or_insert_with(Vec::new).extend(variants);
+        self
+    }
+
+    /// Set default options for all captures.
+    pub fn defaults(mut self, defaults: CaptureDefaults) -> Self {
+        self.request.defaults = Some(defaults);
+        self
+    }
+
+    /// Set the output configuration.
+    pub fn output(mut self, output: ComposeOutputConfig) -> Self {
+        self.request.output = Some(output);
+        self
+    }
+
+    /// Run the request asynchronously.
+    pub fn async_mode(mut self, is_async: bool) -> Self {
+        self.request.is_async = Some(is_async);
+        self
+    }
+
+    /// Set the webhook URL.
+    pub fn webhook_url(mut self, url: impl Into<String>) -> Self {
+        self.request.webhook_url = Some(url.into());
+        self
+    }
+
+    /// Set the webhook secret.
+    pub fn webhook_secret(mut self, secret: impl Into<String>) -> Self {
+        self.request.webhook_secret = Some(secret.into());
+        self
+    }
+
+    /// Build the request, validating all fields.
+    pub fn build(mut self) -> Result<ComposeRequest, AllscreenshotsError> {
+        if self.request.captures.is_some() {
+            self.request.captures_mode = Some(true);
+        }
+        if self.request.variants.is_some() {
+            self.request.variants_mode = Some(true);
+        }
+        self.request.validate()?;
+        Ok(self.request)
+    }
+}
+
+impl ComposeRequest {
+    /// Number of screenshots the request captures.
+    pub fn capture_count(&self) -> usize {
+        match (&self.captures, &self.variants) {
+            (Some(captures), _) => captures.len(),
+            (None, Some(variants)) => variants.len(),
+            (None, None) => 0,
+        }
+    }
+}
+
+impl Validate for ComposeRequest {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        match (&self.captures, &self.variants) {
+            (Some(captures), None) => {
+                check_list_len(errors, path, "captures", captures, MAX_COMPOSE_CAPTURES);
+                for (i, capture) in captures.iter().enumerate() {
+                    capture.collect_violations(&index_path(path, "captures", i), errors);
+                }
+            }
+            (None, Some(variants)) => {
+                check_http_url(errors, path, "url", self.url.as_deref().unwrap_or(""));
+                check_list_len(errors, path, "variants", variants, MAX_COMPOSE_CAPTURES);
+                for (i, variant) in variants.iter().enumerate() {
+                    variant.collect_violations(&index_path(path, "variants", i), errors);
+                }
+            }
+            (Some(_), Some(_)) => {
+                errors.push(field_path(path, "variants"), "cannot be combined with captures");
+            }
+            (None, None) => {
+                errors.push(field_path(path, "captures"), "either captures or url with variants is required");
+            }
+        }
+        self.defaults.collect_violations(&field_path(path, "defaults"), errors);
+        self.output.collect_violations(&field_path(path, "output"), errors);
+        check_optional_http_url(errors, path, "webhookUrl", self.webhook_url.as_deref());
+    }
 }
 
 /// Individual capture configuration.
@@ -182,8 +343,16 @@ impl CaptureItem {
     }
 }
 
+impl Validate for CaptureItem {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_http_url(errors, path, "url", &self.url);
+        self.viewport.collect_violations(&field_path(path, "viewport"), errors);
+        check_range(errors, path, "delay", self.delay, 0..=30000);
+    }
+}
+
 /// Variant configuration for the same URL.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct VariantConfig {
     /// Custom ID for this variant
@@ -212,6 +381,13 @@ pub struct VariantConfig {
     pub custom_css: Option<String>,
 }
 
+impl Validate for VariantConfig {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        self.viewport.collect_violations(&field_path(path, "viewport"), errors);
+        check_range(errors, path, "delay", self.delay, 0..=30000);
+    }
+}
+
 /// Default options for captures.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -263,6 +439,15 @@ pub struct CaptureDefaults {
     pub block_level: Option<String>,
 }
 
+impl Validate for CaptureDefaults {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        self.viewport.collect_violations(&field_path(path, "viewport"), errors);
+        check_range(errors, path, "quality", self.quality, 1..=100);
+        check_range(errors, path, "delay", self.delay, 0..=30000);
+        check_range(errors, path, "timeout", self.timeout, 1000..=60000);
+    }
+}
+
 /// Output configuration for composed images.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -285,9 +470,9 @@ pub struct ComposeOutputConfig {
     /// Padding around the canvas (0-100)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub padding: Option<i32>,
-    /// Background color (#RRGGBB, #RRGGBBAA, or "transparent")
+    /// Background color
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub background: Option<String>,
+    pub background: Option<Color>,
     /// Vertical alignment
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alignment: Option<Alignment>,
@@ -311,8 +496,141 @@ pub struct ComposeOutputConfig {
     pub shadow: Option<ShadowConfig>,
 }
 
+impl ComposeOutputConfig {
+    /// Create a new builder for ComposeOutputConfig.
+    pub fn builder() -> ComposeOutputConfigBuilder {
+        ComposeOutputConfigBuilder::default()
+    }
+}
+
+/// Builder for ComposeOutputConfig.
+#[derive(Debug, Default)]
+pub struct ComposeOutputConfigBuilder {
+    config: ComposeOutputConfig,
+    invalid: ValidationErrors,
+}
+
+impl ComposeOutputConfigBuilder {
+    /// Set the layout type.
+    pub fn layout(mut self, layout: LayoutType) -> Self {
+        self.config.layout = Some(layout);
+        self
+    }
+
+    /// Set the output format.
+    pub fn format(mut self, format: ImageFormat) -> Self {
+        self.config.format = Some(format);
+        self
+    }
+
+    /// Set the image quality (1-100).
+    pub fn quality(mut self, quality: i32) -> Self {
+        self.config.quality = Some(quality);
+        self
+    }
+
+    /// Set the number of columns (1-10).
+    pub fn columns(mut self, columns: i32) -> Self {
+        self.config.columns = Some(columns);
+        self
+    }
+
+    /// Set the spacing between images (0-100).
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.config.spacing = Some(spacing);
+        self
+    }
+
+    /// Set the padding around the canvas (0-100).
+    pub fn padding(mut self, padding: i32) -> Self {
+        self.config.padding = Some(padding);
+        self
+    }
+
+    /// Set the background color.
+    ///
+    /// Accepts a [`Color`] or a string such as `"#ffffff"`; invalid strings
+    /// are reported by [`build`](Self::build).
+    pub fn background<C>(mut self, background: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Into<AllscreenshotsError>,
+    {
+        match background.try_into() {
+            Ok(color) => self.config.background = Some(color),
+            Err(e) => record_error(&mut self.invalid, "", "background", e.into()),
+        }
+        self
+    }
+
+    /// Set the vertical alignment.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.config.alignment = Some(alignment);
+        self
+    }
+
+    /// Set the maximum width (100-10000).
+    pub fn max_width(mut self, max_width: i32) -> Self {
+        self.config.max_width = Some(max_width);
+        self
+    }
+
+    /// Set the maximum height (100-10000).
+    pub fn max_height(mut self, max_height: i32) -> Self {
+        self.config.max_height = Some(max_height);
+        self
+    }
+
+    /// Set the thumbnail width (50-2000).
+    pub fn thumbnail_width(mut self, thumbnail_width: i32) -> Self {
+        self.config.thumbnail_width = Some(thumbnail_width);
+        self
+    }
+
+    /// Set the label configuration.
+    pub fn labels(mut self, labels: LabelConfig) -> Self {
+        self.config.labels = Some(labels);
+        self
+    }
+
+    /// Set the border configuration.
+    pub fn border(mut self, border: BorderConfig) -> Self {
+        self.config.border = Some(border);
+        self
+    }
+
+    /// Set the shadow configuration.
+    pub fn shadow(mut self, shadow: ShadowConfig) -> Self {
+        self.config.shadow = Some(shadow);
+        self
+    }
+
+    /// Build the configuration, validating all fields.
+    pub fn build(self) -> Result<ComposeOutputConfig, AllscreenshotsError> {
+        let mut errors = self.invalid;
+        self.config.collect_violations("", &mut errors);
+        errors.into_result()?;
+        Ok(self.config)
+    }
+}
+
+impl Validate for ComposeOutputConfig {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_range(errors, path, "quality", self.quality, 1..=100);
+        check_range(errors, path, "columns", self.columns, 1..=10);
+        check_range(errors, path, "spacing", self.spacing, 0..=100);
+        check_range(errors, path, "padding", self.padding, 0..=100);
+        check_range(errors, path, "maxWidth", self.max_width, 100..=10000);
+        check_range(errors, path, "maxHeight", self.max_height, 100..=10000);
+        check_range(errors, path, "thumbnailWidth", self.thumbnail_width, 50..=2000);
+        self.labels.collect_violations(&field_path(path, "labels"), errors);
+        self.border.collect_violations(&field_path(path, "border"), errors);
+        self.shadow.collect_violations(&field_path(path, "shadow"), errors);
+    }
+}
+
 /// Label configuration for composed images.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct LabelConfig {
     /// Enable labels
@@ -323,14 +641,78 @@ pub struct LabelConfig {
     pub font_size: Option<i32>,
     /// Font color
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
+    pub color: Option<Color>,
     /// Label position
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub position: Option<String>,
+    pub position: Option<LabelPosition>,
+}
+
+impl LabelConfig {
+    /// Create a new builder for LabelConfig.
+    pub fn builder() -> LabelConfigBuilder {
+        LabelConfigBuilder::default()
+    }
+}
+
+/// Builder for LabelConfig.
+#[derive(Debug, Default)]
+pub struct LabelConfigBuilder {
+    config: LabelConfig,
+    invalid: ValidationErrors,
+}
+
+impl LabelConfigBuilder {
+    /// Enable or disable labels.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.config.enabled = Some(enabled);
+        self
+    }
+
+    /// Set the font size.
+    pub fn font_size(mut self, font_size: i32) -> Self {
+        self.config.font_size = Some(font_size);
+        self
+    }
+
+    /// Set the font color.
+    ///
+    /// Accepts a [`Color`] or a string such as `"#ffffff"`; invalid strings
+    /// are reported by [`build`](Self::build).
+    pub fn color<C>(mut self, color: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Into<AllscreenshotsError>,
+    {
+        match color.try_into() {
+            Ok(color) => self.config.color = Some(color),
+            Err(e) => record_error(&mut self.invalid, "", "color", e.into()),
+        }
+        self
+    }
+
+    /// Set the label position.
+    pub fn position(mut self, position: LabelPosition) -> Self {
+        self.config.position = Some(position);
+        self
+    }
+
+    /// Build the configuration, validating all fields.
+    pub fn build(self) -> Result<LabelConfig, AllscreenshotsError> {
+        let mut errors = self.invalid;
+        self.config.collect_violations("", &mut errors);
+        errors.into_result()?;
+        Ok(self.config)
+    }
+}
+
+impl Validate for LabelConfig {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_min(errors, path, "fontSize", self.font_size, 1);
+    }
 }
 
 /// Border configuration for composed images.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BorderConfig {
     /// Enable border
@@ -341,14 +723,79 @@ pub struct BorderConfig {
     pub width: Option<i32>,
     /// Border color
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
+    pub color: Option<Color>,
     /// Border radius
     #[serde(skip_serializing_if = "Option::is_none")]
     pub radius: Option<i32>,
 }
 
+impl BorderConfig {
+    /// Create a new builder for BorderConfig.
+    pub fn builder() -> BorderConfigBuilder {
+        BorderConfigBuilder::default()
+    }
+}
+
+/// Builder for BorderConfig.
+#[derive(Debug, Default)]
+pub struct BorderConfigBuilder {
+    config: BorderConfig,
+    invalid: ValidationErrors,
+}
+
+impl BorderConfigBuilder {
+    /// Enable or disable the border.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.config.enabled = Some(enabled);
+        self
+    }
+
+    /// Set the border width.
+    pub fn width(mut self, width: i32) -> Self {
+        self.config.width = Some(width);
+        self
+    }
+
+    /// Set the border color.
+    ///
+    /// Accepts a [`Color`] or a string such as `"#ffffff"`; invalid strings
+    /// are reported by [`build`](Self::build).
+    pub fn color<C>(mut self, color: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Into<AllscreenshotsError>,
+    {
+        match color.try_into() {
+            Ok(color) => self.config.color = Some(color),
+            Err(e) => record_error(&mut self.invalid, "", "color", e.into()),
+        }
+        self
+    }
+
+    /// Set the border radius.
+    pub fn radius(mut self, radius: i32) -> Self {
+        self.config.radius = Some(radius);
+        self
+    }
+
+    /// Build the configuration, validating all fields.
+    pub fn build(self) -> Result<BorderConfig, AllscreenshotsError> {
+        let mut errors = self.invalid;
+        self.config.collect_violations("", &mut errors);
+        errors.into_result()?;
+        Ok(self.config)
+    }
+}
+
+impl Validate for BorderConfig {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_min(errors, path, "width", self.width, 0);
+        check_min(errors, path, "radius", self.radius, 0);
+    }
+}
+
 /// Shadow configuration for composed images.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ShadowConfig {
     /// Enable shadow
@@ -359,7 +806,7 @@ pub struct ShadowConfig {
     pub blur: Option<i32>,
     /// Shadow color
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
+    pub color: Option<Color>,
     /// Shadow offset X
     #[serde(skip_serializing_if = "Option::is_none")]
     pub offset_x: Option<i32>,
@@ -368,6 +815,71 @@ pub struct ShadowConfig {
     pub offset_y: Option<i32>,
 }
 
+impl ShadowConfig {
+    /// Create a new builder for ShadowConfig.
+    pub fn builder() -> ShadowConfigBuilder {
+        ShadowConfigBuilder::default()
+    }
+}
+
+/// Builder for ShadowConfig.
+#[derive(Debug, Default)]
+pub struct ShadowConfigBuilder {
+    config: ShadowConfig,
+    invalid: ValidationErrors,
+}
+
+impl ShadowConfigBuilder {
+    /// Enable or disable the shadow.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.config.enabled = Some(enabled);
+        self
+    }
+
+    /// Set the shadow blur.
+    pub fn blur(mut self, blur: i32) -> Self {
+        self.config.blur = Some(blur);
+        self
+    }
+
+    /// Set the shadow color.
+    ///
+    /// Accepts a [`Color`] or a string such as `"#ffffff"`; invalid strings
+    /// are reported by [`build`](Self::build).
+    pub fn color<C>(mut self, color: C) -> Self
+    where
+        C: TryInto<Color>,
+        C::Error: Into<AllscreenshotsError>,
+    {
+        match color.try_into() {
+            Ok(color) => self.config.color = Some(color),
+            Err(e) => record_error(&mut self.invalid, "", "color", e.into()),
+        }
+        self
+    }
+
+    /// Set the shadow offset.
+    pub fn offset(mut self, x: i32, y: i32) -> Self {
+        self.config.offset_x = Some(x);
+        self.config.offset_y = Some(y);
+        self
+    }
+
+    /// Build the configuration, validating all fields.
+    pub fn build(self) -> Result<ShadowConfig, AllscreenshotsError> {
+        let mut errors = self.invalid;
+        self.config.collect_violations("", &mut errors);
+        errors.into_result()?;
+        Ok(self.config)
+    }
+}
+
+impl Validate for ShadowConfig {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_min(errors, path, "blur", self.blur, 0);
+    }
+}
+
 /// Response for synchronous compose request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -395,13 +907,16 @@ pub struct ComposeResponse {
 }
 
 /// Metadata for compose response.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ComposeMetadata {
     /// Number of captures
     pub capture_count: Option<i32>,
     /// Layout type used
     pub layout_type: Option<String>,
+    /// Any other metadata returned by the API
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Status response for async compose job.
@@ -454,6 +969,108 @@ pub struct ComposeJobSummaryResponse {
     pub completed_at: Option<String>,
 }
 
+/// Request for a layout preview.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{LayoutPreviewRequest, LayoutType};
+///
+/// let request = LayoutPreviewRequest::new(LayoutType::Masonry, 3)
+///     .aspect_ratios(vec![1.78, 0.56, 1.0])
+///     .canvas_size(1200, 800);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutPreviewRequest {
+    /// Layout type
+    pub layout: LayoutType,
+    /// Number of images to place
+    pub image_count: i32,
+    /// Canvas width
+    pub canvas_width: Option<i32>,
+    /// Canvas height
+    pub canvas_height: Option<i32>,
+    /// Aspect ratio (width / height) of each image
+    pub aspect_ratios: Option<Vec<f32>>,
+}
+
+impl LayoutPreviewRequest {
+    /// Create a preview request for `image_count` images.
+    pub fn new(layout: LayoutType, image_count: i32) -> Self {
+        Self {
+            layout,
+            image_count,
+            canvas_width: None,
+            canvas_height: None,
+            aspect_ratios: None,
+        }
+    }
+
+    /// Set the canvas width.
+    pub fn canvas_width(mut self, width: i32) -> Self {
+        self.canvas_width = Some(width);
+        self
+    }
+
+    /// Set the canvas height.
+    pub fn canvas_height(mut self, height: i32) -> Self {
+        self.canvas_height = Some(height);
+        self
+    }
+
+    /// Set the canvas size.
+    pub fn canvas_size(self, width: i32, height: i32) -> Self {
+        self.canvas_width(width).canvas_height(height)
+    }
+
+    /// Set the aspect ratio of each image, one per image.
+    pub fn aspect_ratios(mut self, aspect_ratios: Vec<f32>) -> Self {
+        self.aspect_ratios = Some(aspect_ratios);
+        self
+    }
+
+    /// Query parameters for the preview endpoint, unencoded.
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let layout = serde_json::to_value(self.layout)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let mut pairs = vec![("layout", layout), ("image_count", self.image_count.to_string())];
+        if let Some(width) = self.canvas_width {
+            pairs.push(("canvas_width", width.to_string()));
+        }
+        if let Some(height) = self.canvas_height {
+            pairs.push(("canvas_height", height.to_string()));
+        }
+        if let Some(ratios) = &self.aspect_ratios {
+            let ratios: Vec<String> = ratios.iter().map(f32::to_string).collect();
+            pairs.push(("aspect_ratios", ratios.join(",")));
+        }
+        pairs
+    }
+}
+
+impl Validate for LayoutPreviewRequest {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_range(errors, path, "imageCount", Some(self.image_count), 1..=MAX_COMPOSE_CAPTURES as i32);
+        check_range(errors, path, "canvasWidth", self.canvas_width, 100..=10000);
+        check_range(errors, path, "canvasHeight", self.canvas_height, 100..=10000);
+        if let Some(ratios) = &self.aspect_ratios {
+            if ratios.len() != self.image_count.max(0) as usize {
+                errors.push(
+                    field_path(path, "aspectRatios"),
+                    format!("must contain one entry per image (expected {}, got {})", self.image_count, ratios.len()),
+                );
+            }
+            for (i, ratio) in ratios.iter().enumerate() {
+                if !ratio.is_finite() || *ratio <= 0.0 {
+                    errors.push(index_path(path, "aspectRatios", i), "must be a positive number");
+                }
+            }
+        }
+    }
+}
+
 /// Layout preview response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -469,7 +1086,31 @@ pub struct LayoutPreviewResponse {
     /// Placement positions
     pub placements: Vec<PlacementPreview>,
     /// Additional metadata
-    pub metadata: Option<serde_json::Value>,
+    pub metadata: Option<LayoutMetadata>,
+}
+
+/// Metadata of a layout preview.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutMetadata {
+    /// Number of images placed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_count: Option<i32>,
+    /// Number of columns, for column-based layouts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<i32>,
+    /// Number of rows, for row-based layouts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<i32>,
+    /// Spacing between images in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<i32>,
+    /// Padding around the canvas in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<i32>,
+    /// Any other metadata returned by the API
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Preview of image placement.
@@ -489,3 +1130,234 @@ pub struct PlacementPreview {
     /// Label
     pub label: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_valid_request() {
+        let request = ComposeRequest::with_captures(vec![
+            CaptureItem::new("https://example.com"),
+            CaptureItem::new("https://example.org"),
+        ])
+        .with_output(ComposeOutputConfig {
+            columns: Some(2),
+            background: Some(Color::WHITE),
+            ..Default::default()
+        });
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_too_many_captures() {
+        let captures = (0..=MAX_COMPOSE_CAPTURES)
+            .map(|i| CaptureItem::new(format!("https://example.com/{}", i)))
+            .collect();
+        let errors = ComposeRequest::with_captures(captures).violations();
+        assert!(errors.has_field("captures"));
+    }
+
+    #[test]
+    fn test_validate_variants_require_url() {
+        let mut request = ComposeRequest::with_variants(
+            "https://example.com".to_string(),
+            vec![VariantConfig {
+                device: Some("iPhone 14".to_string()),
+                ..Default::default()
+            }],
+        );
+        assert!(request.validate().is_ok());
+
+        request.url = None;
+        assert!(request.violations().has_field("url"));
+    }
+
+    #[test]
+    fn test_validate_output_config() {
+        let request = ComposeRequest::with_captures(vec![CaptureItem::new("https://example.com")])
+            .with_output(ComposeOutputConfig {
+                columns: Some(11),
+                spacing: Some(-1),
+                padding: Some(101),
+                border: Some(BorderConfig {
+                    enabled: Some(true),
+                    width: Some(-2),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+
+        let errors = request.violations();
+        assert_eq!(errors.len(), 4);
+        assert!(errors.has_field("output.columns"));
+        assert!(errors.has_field("output.spacing"));
+        assert!(errors.has_field("output.padding"));
+        assert!(errors.has_field("output.border.width"));
+    }
+
+    #[test]
+    fn test_layout_preview_request() {
+        let request = LayoutPreviewRequest::new(LayoutType::Partitioning, 2)
+            .aspect_ratios(vec![1.5, 0.75])
+            .canvas_size(1200, 800);
+        assert!(request.validate().is_ok());
+        assert_eq!(
+            request.query_pairs(),
+            vec![
+                ("layout", "PARTITIONING".to_string()),
+                ("image_count", "2".to_string()),
+                ("canvas_width", "1200".to_string()),
+                ("canvas_height", "800".to_string()),
+                ("aspect_ratios", "1.5,0.75".to_string()),
+            ]
+        );
+
+        let errors = LayoutPreviewRequest::new(LayoutType::Grid, MAX_COMPOSE_CAPTURES as i32 + 1)
+            .aspect_ratios(vec![1.0, f32::NAN])
+            .canvas_width(50)
+            .violations();
+        assert_eq!(errors.len(), 4);
+        assert!(errors.has_field("imageCount"));
+        assert!(errors.has_field("canvasWidth"));
+        assert!(errors.has_field("aspectRatios"));
+        assert!(errors.has_field("aspectRatios[1]"));
+    }
+
+    #[test]
+    fn test_constructors_set_mode() {
+        let request = ComposeRequest::with_captures(vec![CaptureItem::new("https://example.com")]);
+        assert_eq!(request.captures_mode, Some(true));
+        assert_eq!(request.variants_mode, None);
+
+        let request = ComposeRequest::with_variants("https://example.com".to_string(), vec![]);
+        assert_eq!(request.variants_mode, Some(true));
+        assert_eq!(request.captures_mode, None);
+    }
+
+    #[test]
+    fn test_builder_captures() {
+        let request = ComposeRequest::builder()
+            .capture(CaptureItem::new("https://example.com"))
+            .captures(vec![CaptureItem::new("https://example.org")])
+            .async_mode(true)
+            .webhook_url("https://example.com/webhook")
+            .webhook_secret("secret")
+            .build()
+            .unwrap();
+
+        assert_eq!(request.captures.as_ref().unwrap().len(), 2);
+        assert_eq!(request.captures_mode, Some(true));
+        assert_eq!(request.variants_mode, None);
+        assert_eq!(request.is_async, Some(true));
+        assert_eq!(request.webhook_url.as_deref(), Some("https://example.com/webhook"));
+        assert_eq!(request.webhook_secret.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn test_builder_variants() {
+        let request = ComposeRequest::builder()
+            .url("https://example.com")
+            .variant(VariantConfig {
+                device: Some("Desktop HD".to_string()),
+                ..Default::default()
+            })
+            .variant(VariantConfig {
+                device: Some("iPhone 14".to_string()),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(request.variants.as_ref().unwrap().len(), 2);
+        assert_eq!(request.variants_mode, Some(true));
+        assert_eq!(request.captures_mode, None);
+    }
+
+    #[test]
+    fn test_builder_rejects_mixed_modes() {
+        let result = ComposeRequest::builder()
+            .capture(CaptureItem::new("https://example.com"))
+            .url("https://example.com")
+            .variant(VariantConfig::default())
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_output_config_builders() {
+        let output = ComposeOutputConfig::builder()
+            .layout(LayoutType::Grid)
+            .columns(3)
+            .spacing(10)
+            .padding(20)
+            .background("#ffffff")
+            .labels(
+                LabelConfig::builder()
+                    .enabled(true)
+                    .font_size(14)
+                    .color(Color::BLACK)
+                    .position(LabelPosition::Top)
+                    .build()
+                    .unwrap(),
+            )
+            .border(BorderConfig::builder().enabled(true).width(2).color("#cccccc").radius(8).build().unwrap())
+            .shadow(ShadowConfig::builder().enabled(true).blur(10).color("#00000040").offset(0, 4).build().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(output.columns, Some(3));
+        assert_eq!(output.shadow.as_ref().unwrap().offset_y, Some(4));
+        assert_eq!(output.border.as_ref().unwrap().radius, Some(8));
+
+        assert_eq!(output.background, Some(Color::WHITE));
+        assert_eq!(output.labels.as_ref().unwrap().position, Some(LabelPosition::Top));
+
+        assert!(ComposeOutputConfig::builder().columns(0).build().is_err());
+        assert!(LabelConfig::builder().font_size(0).build().is_err());
+        assert!(BorderConfig::builder().width(-1).build().is_err());
+        assert!(ShadowConfig::builder().blur(-1).build().is_err());
+    }
+
+    #[test]
+    fn test_builders_report_invalid_colors() {
+        let result = ComposeOutputConfig::builder().background("#12345").columns(11).build();
+        match result {
+            Err(AllscreenshotsError::InvalidRequest(errors)) => {
+                assert_eq!(errors.len(), 2);
+                assert!(errors.has_field("background"));
+                assert!(errors.has_field("columns"));
+            }
+            other => panic!("Expected InvalidRequest, got {:?}", other),
+        }
+
+        assert!(LabelConfig::builder().color("rgba(0,0,0,0.5)").build().is_err());
+        assert!(BorderConfig::builder().color("#ccc").build().is_ok());
+        assert!(ShadowConfig::builder().color("not-a-color").build().is_err());
+    }
+
+    #[test]
+    fn test_output_config_serialization() {
+        let output = ComposeOutputConfig::builder()
+            .background("transparent")
+            .labels(LabelConfig::builder().color("#ffffff80").position(LabelPosition::Bottom).build().unwrap())
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["background"], "transparent");
+        assert_eq!(json["labels"]["color"], "#ffffff80");
+        assert_eq!(json["labels"]["position"], "bottom");
+
+        let bad = r##"{"background": "#zzzzzz"}"##;
+        assert!(serde_json::from_str::<ComposeOutputConfig>(bad).is_err());
+    }
+
+    #[test]
+    fn test_validate_missing_captures() {
+        let mut request = ComposeRequest::with_captures(vec![]);
+        assert!(request.violations().has_field("captures"));
+
+        request.captures = None;
+        assert!(request.violations().has_field("captures"));
+    }
+}
@@ -0,0 +1,476 @@
+//! Cron expressions for scheduled screenshots.
+
+use super::timezone::Timezone;
+use crate::error::AllscreenshotsError;
+use chrono::{DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// How many years ahead to search for the next fire time before giving up.
+///
+/// 28 years is one full cycle of the Gregorian calendar's weekday/leap-year
+/// pattern, so any expression that can fire at all fires within this window.
+const MAX_SEARCH_YEARS: i32 = 28;
+
+/// A parsed and validated five-field cron expression.
+///
+/// The fields are `minute hour day-of-month month day-of-week`. Each field
+/// accepts `*`, single values, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/15`, `9-17/2`). Months and weekdays also accept three-letter names
+/// (`JAN`, `MON`), and both `0` and `7` mean Sunday. As in standard cron, when
+/// both day-of-month and day-of-week are restricted, a day matches if
+/// *either* field matches.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{CronSchedule, CreateScheduleRequest};
+///
+/// let cron: CronSchedule = "*/15 9-17 * * MON-FRI".parse().unwrap();
+/// assert_eq!(cron.to_string(), "*/15 9-17 * * MON-FRI");
+///
+/// let request = CreateScheduleRequest::new(
+///     "Morning capture",
+///     "https://github.com",
+///     CronSchedule::weekdays_at(9, 0).unwrap(),
+/// );
+/// assert_eq!(request.schedule, "0 9 * * 1-5");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parse and validate a cron expression.
+    pub fn parse(expression: &str) -> Result<Self, AllscreenshotsError> {
+        let invalid = |reason: String| {
+            AllscreenshotsError::ValidationError(format!(
+                "Invalid cron expression '{}': {}",
+                expression, reason
+            ))
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(invalid(format!("expected 5 fields, found {}", fields.len())));
+        }
+
+        let minutes = parse_field(fields[0], 0, 59, &[]).map_err(|e| invalid(format!("minute {}", e)))?;
+        let hours = parse_field(fields[1], 0, 23, &[]).map_err(|e| invalid(format!("hour {}", e)))?;
+        let days_of_month =
+            parse_field(fields[2], 1, 31, &[]).map_err(|e| invalid(format!("day-of-month {}", e)))?;
+        let months =
+            parse_field(fields[3], 1, 12, MONTH_NAMES).map_err(|e| invalid(format!("month {}", e)))?;
+        let mut days_of_week =
+            parse_field(fields[4], 0, 7, WEEKDAY_NAMES).map_err(|e| invalid(format!("day-of-week {}", e)))?;
+
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: fields.join(" "),
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            day_of_month_restricted: !fields[2].starts_with('*'),
+            day_of_week_restricted: !fields[4].starts_with('*'),
+        })
+    }
+
+    /// Fire at the start of every hour.
+    pub fn every_hour() -> Self {
+        Self::parse("0 * * * *").expect("valid cron expression")
+    }
+
+    /// Fire every day at the given time.
+    pub fn daily_at(hour: u32, minute: u32) -> Result<Self, AllscreenshotsError> {
+        Self::parse(&format!("{} {} * * *", minute, hour))
+    }
+
+    /// Fire Monday through Friday at the given time.
+    pub fn weekdays_at(hour: u32, minute: u32) -> Result<Self, AllscreenshotsError> {
+        Self::parse(&format!("{} {} * * 1-5", minute, hour))
+    }
+
+    /// The normalized cron expression.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Returns `true` if the schedule fires at the given local date and time.
+    ///
+    /// Seconds are ignored.
+    pub fn matches(&self, local: &NaiveDateTime) -> bool {
+        self.matches_date(&local.date())
+            && has_bit(self.hours, local.hour())
+            && has_bit(self.minutes, local.minute())
+    }
+
+    /// Compute the first fire time strictly after `after`, in `after`'s timezone.
+    ///
+    /// Local times that fall into a DST gap are skipped; local times that occur
+    /// twice when clocks go back fire once, at the earlier instant. Returns
+    /// `None` if the expression can never fire (e.g. `0 0 30 2 *`).
+    pub fn next_after<Z: TimeZone>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let tz = after.timezone();
+        let start = after.naive_local();
+        let mut candidate = start.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let last_year = start.year() + MAX_SEARCH_YEARS;
+
+        while candidate.year() <= last_year {
+            let date = candidate.date();
+            if !has_bit(self.months, date.month()) {
+                candidate = first_of_next_month(&date)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_date(&date) {
+                candidate = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !has_bit(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !has_bit(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+                continue;
+            }
+
+            match tz.from_local_datetime(&candidate) {
+                LocalResult::Single(dt) if dt > *after => return Some(dt),
+                // The repeated hour only fires at its first occurrence
+                LocalResult::Ambiguous(earliest, _) if earliest > *after => return Some(earliest),
+                _ => {}
+            }
+            candidate += Duration::minutes(1);
+        }
+
+        None
+    }
+
+    /// Compute up to `count` fire times strictly after `after`.
+    pub fn upcoming<Z: TimeZone>(&self, after: &DateTime<Z>, count: usize) -> Vec<DateTime<Z>> {
+        let mut runs = Vec::with_capacity(count);
+        let mut cursor = after.clone();
+        while runs.len() < count {
+            match self.next_after(&cursor) {
+                Some(next) => {
+                    cursor = next.clone();
+                    runs.push(next);
+                }
+                None => break,
+            }
+        }
+        runs
+    }
+
+    /// Compute up to `count` fire times after `after` in the given timezone.
+    pub fn upcoming_in(
+        &self,
+        timezone: &Timezone,
+        after: &DateTime<Utc>,
+        count: usize,
+    ) -> Vec<DateTime<chrono_tz::Tz>> {
+        self.upcoming(&after.with_timezone(&timezone.tz()), count)
+    }
+
+    fn matches_date(&self, date: &NaiveDate) -> bool {
+        if !has_bit(self.months, date.month()) {
+            return false;
+        }
+        let dom = has_bit(self.days_of_month, date.day());
+        let dow = has_bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = AllscreenshotsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = AllscreenshotsError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<CronSchedule> for String {
+    fn from(cron: CronSchedule) -> Self {
+        cron.expression
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+fn has_bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn first_of_next_month(date: &NaiveDate) -> Option<NaiveDate> {
+    if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+}
+
+/// Parse one cron field into a bitset of allowed values.
+///
+/// `names`, if given, are aliases for consecutive values starting at `min`.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut set = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("field has invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("field has a step of 0".to_string());
+                }
+                if step > max {
+                    return Err(format!("field has a step of {}, larger than its maximum {}", step, max));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, min, max, names)?, parse_value(end, min, max, names)?)
+        } else {
+            let start = parse_value(range, min, max, names)?;
+            // `5/10` means "from 5 to the end, every 10"
+            (start, if part.contains('/') { max } else { start })
+        };
+
+        if start > end {
+            return Err(format!("field has a descending range '{}'", range));
+        }
+
+        let mut value = start;
+        while value <= end {
+            set |= 1 << value;
+            value += step;
+        }
+    }
+
+    Ok(set)
+}
+
+fn parse_value(value: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    if let Some(index) = names.iter().position(|name| name.eq_ignore_ascii_case(value)) {
+        return Ok(min + index as u32);
+    }
+
+    let parsed: u32 = value
+        .parse()
+        .map_err(|_| format!("field has invalid value '{}'", value))?;
+    if parsed < min || parsed > max {
+        return Err(format!("value {} is out of range {}-{}", parsed, min, max));
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_valid_expressions() {
+        for expr in [
+            "* * * * *",
+            "0 9 * * *",
+            "*/15 9-17 * * MON-FRI",
+            "0 0 1,15 * *",
+            "30 2 * jan,jul sun",
+            "5/10 * * * 7",
+        ] {
+            assert!(CronSchedule::parse(expr).is_ok(), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_expressions() {
+        for expr in [
+            "",
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "1/4294967295 * * * *",
+            "* */25 * * *",
+            "5-1 * * * *",
+            "abc * * * *",
+        ] {
+            assert!(CronSchedule::parse(expr).is_err(), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_error_names_field() {
+        let err = CronSchedule::parse("0 25 * * *").unwrap_err();
+        assert!(err.to_string().contains("hour"));
+    }
+
+    #[test]
+    fn test_huge_step_is_rejected() {
+        let err = CronSchedule::parse("1/4294967295 * * * *").unwrap_err();
+        assert!(err.to_string().contains("minute"));
+        assert!(CronSchedule::parse("*/59 * * * *").is_ok());
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(CronSchedule::every_hour().expression(), "0 * * * *");
+        assert_eq!(CronSchedule::daily_at(9, 30).unwrap().expression(), "30 9 * * *");
+        assert_eq!(CronSchedule::weekdays_at(18, 0).unwrap().expression(), "0 18 * * 1-5");
+        assert!(CronSchedule::daily_at(24, 0).is_err());
+    }
+
+    #[test]
+    fn test_expression_is_normalized() {
+        let cron = CronSchedule::parse("  0   9 * *   * ").unwrap();
+        assert_eq!(cron.expression(), "0 9 * * *");
+    }
+
+    #[test]
+    fn test_next_after_daily() {
+        let cron = CronSchedule::daily_at(9, 0).unwrap();
+        let next = cron.next_after(&utc("2024-03-10T08:59:30Z")).unwrap();
+        assert_eq!(next, utc("2024-03-10T09:00:00Z"));
+
+        let next = cron.next_after(&utc("2024-03-10T09:00:00Z")).unwrap();
+        assert_eq!(next, utc("2024-03-11T09:00:00Z"));
+    }
+
+    #[test]
+    fn test_upcoming_weekdays() {
+        let cron = CronSchedule::weekdays_at(9, 0).unwrap();
+        // 2024-03-08 is a Friday
+        let runs = cron.upcoming(&utc("2024-03-08T10:00:00Z"), 3);
+        assert_eq!(
+            runs,
+            vec![
+                utc("2024-03-11T09:00:00Z"),
+                utc("2024-03-12T09:00:00Z"),
+                utc("2024-03-13T09:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // The 13th of the month or any Friday
+        let cron = CronSchedule::parse("0 0 13 * 5").unwrap();
+        let runs = cron.upcoming(&utc("2024-09-01T00:00:00Z"), 3);
+        assert_eq!(
+            runs,
+            vec![
+                utc("2024-09-06T00:00:00Z"),
+                utc("2024-09-13T00:00:00Z"),
+                utc("2024-09-20T00:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_leap_day() {
+        let cron = CronSchedule::parse("0 12 29 2 *").unwrap();
+        let next = cron.next_after(&utc("2024-03-01T00:00:00Z")).unwrap();
+        assert_eq!(next, utc("2028-02-29T12:00:00Z"));
+    }
+
+    #[test]
+    fn test_never_fires() {
+        let cron = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert!(cron.next_after(&utc("2024-01-01T00:00:00Z")).is_none());
+    }
+
+    #[test]
+    fn test_upcoming_in_timezone() {
+        let cron = CronSchedule::daily_at(9, 0).unwrap();
+        let tz = Timezone::parse("America/New_York").unwrap();
+        let runs = cron.upcoming_in(&tz, &utc("2024-03-09T00:00:00Z"), 2);
+        // DST starts on 2024-03-10 in New York
+        assert_eq!(runs[0].with_timezone(&Utc), utc("2024-03-09T14:00:00Z"));
+        assert_eq!(runs[1].with_timezone(&Utc), utc("2024-03-10T13:00:00Z"));
+    }
+
+    #[test]
+    fn test_dst_gap_is_skipped() {
+        // 02:30 does not exist in New York on 2024-03-10
+        let cron = CronSchedule::daily_at(2, 30).unwrap();
+        let tz = Timezone::parse("America/New_York").unwrap();
+        let runs = cron.upcoming_in(&tz, &utc("2024-03-09T12:00:00Z"), 2);
+        assert_eq!(runs[0].with_timezone(&Utc), utc("2024-03-11T06:30:00Z"));
+    }
+
+    #[test]
+    fn test_dst_overlap_fires_once() {
+        // 01:30 happens twice in New York on 2024-11-03, at 05:30Z and 06:30Z
+        let cron = CronSchedule::daily_at(1, 30).unwrap();
+        let tz = Timezone::parse("America/New_York").unwrap();
+        let runs = cron.upcoming_in(&tz, &utc("2024-11-02T12:00:00Z"), 2);
+        assert_eq!(runs[0].with_timezone(&Utc), utc("2024-11-03T05:30:00Z"));
+        assert_eq!(runs[1].with_timezone(&Utc), utc("2024-11-04T06:30:00Z"));
+
+        // Starting between the two occurrences skips the repeat as well
+        let runs = cron.upcoming_in(&tz, &utc("2024-11-03T06:00:00Z"), 1);
+        assert_eq!(runs[0].with_timezone(&Utc), utc("2024-11-04T06:30:00Z"));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let cron = CronSchedule::weekdays_at(9, 0).unwrap();
+        let json = serde_json::to_string(&cron).unwrap();
+        assert_eq!(json, "\"0 9 * * 1-5\"");
+        let parsed: CronSchedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, cron);
+        assert!(serde_json::from_str::<CronSchedule>("\"bogus\"").is_err());
+    }
+}
@@ -6,6 +6,10 @@ mod compose;
 mod schedule;
 mod usage;
 mod common;
+mod color;
+mod cron;
+mod timezone;
+mod pagination;
 
 pub use screenshot::*;
 pub use bulk::*;
@@ -13,3 +17,7 @@ pub use compose::*;
 pub use schedule::*;
 pub use usage::*;
 pub use common::*;
+pub use color::*;
+pub use cron::*;
+pub use timezone::*;
+pub use pagination::*;
@@ -0,0 +1,355 @@
+//! Filtering and pagination for list endpoints.
+
+use crate::validation::*;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Maximum number of items the API returns per page.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Filters and paging for list endpoints such as
+/// [`AllscreenshotsClient::list_jobs_with`](crate::AllscreenshotsClient::list_jobs_with).
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::ListOptions;
+/// use chrono::{Duration, Utc};
+///
+/// let options = ListOptions::new()
+///     .status("FAILED")
+///     .created_after(Utc::now() - Duration::days(7))
+///     .url_contains("example.com")
+///     .page_size(100);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    /// Only include items with this status
+    pub status: Option<String>,
+    /// Only include items created at or after this time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only include items created before this time
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only include items whose URL contains this text
+    pub url_contains: Option<String>,
+    /// Number of items per page (1-100)
+    pub page_size: Option<u32>,
+    /// Cursor returned with the previous page
+    pub cursor: Option<String>,
+    /// Number of items to skip
+    pub offset: Option<u32>,
+}
+
+impl ListOptions {
+    /// Create options without any filters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only include items with this status, e.g. `"FAILED"`.
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    /// Only include items created at or after this time.
+    pub fn created_after(mut self, time: DateTime<Utc>) -> Self {
+        self.created_after = Some(time);
+        self
+    }
+
+    /// Only include items created before this time.
+    pub fn created_before(mut self, time: DateTime<Utc>) -> Self {
+        self.created_before = Some(time);
+        self
+    }
+
+    /// Only include items whose URL contains this text.
+    pub fn url_contains(mut self, text: impl Into<String>) -> Self {
+        self.url_contains = Some(text.into());
+        self
+    }
+
+    /// Set the number of items per page.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Continue from the cursor returned with a previous page.
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Skip this many items.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Query parameters for a list endpoint, unencoded.
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(status) = &self.status {
+            pairs.push(("status", status.clone()));
+        }
+        self.created_range().push_pairs(&mut pairs);
+        if let Some(text) = &self.url_contains {
+            pairs.push(("url_contains", text.clone()));
+        }
+        self.paging().push_pairs(&mut pairs);
+        pairs
+    }
+
+    fn created_range(&self) -> TimeRange {
+        TimeRange {
+            names: ["created_after", "created_before"],
+            fields: ["createdAfter", "createdBefore"],
+            after: self.created_after,
+            before: self.created_before,
+        }
+    }
+
+    fn paging(&self) -> Paging<'_> {
+        Paging { size_field: "pageSize", size: self.page_size, cursor: &self.cursor, offset: self.offset }
+    }
+}
+
+impl Validate for ListOptions {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        self.paging().collect_violations(path, errors);
+        self.created_range().collect_violations(path, errors);
+    }
+}
+
+impl PageOptions for ListOptions {
+    fn offset(&self) -> Option<u32> {
+        self.offset
+    }
+
+    fn with_position(&self, cursor: Option<String>, offset: Option<u32>) -> Self {
+        Self { cursor, offset, ..self.clone() }
+    }
+}
+
+/// Options of a paged endpoint, which continue either from a cursor or an offset.
+pub(crate) trait PageOptions: Clone {
+    /// Number of items skipped
+    fn offset(&self) -> Option<u32>;
+
+    /// The same filters, continuing from `cursor` or `offset`.
+    fn with_position(&self, cursor: Option<String>, offset: Option<u32>) -> Self;
+}
+
+/// Options for the page after one holding `len` items, or `None` if that
+/// page was the last one.
+///
+/// Follows `next_cursor` when present; otherwise advances the offset while
+/// `more` reports more items after the new offset.
+pub(crate) fn next_page_options<O: PageOptions>(
+    current: &O,
+    len: usize,
+    next_cursor: Option<&str>,
+    more: impl FnOnce(u32) -> bool,
+) -> Option<O> {
+    if len == 0 {
+        return None;
+    }
+    if let Some(cursor) = next_cursor {
+        return Some(current.with_position(Some(cursor.to_string()), None));
+    }
+    let offset = current.offset().unwrap_or(0) + len as u32;
+    more(offset).then(|| current.with_position(None, Some(offset)))
+}
+
+/// Page size, cursor and offset of a paged request.
+pub(crate) struct Paging<'a> {
+    /// Name of the page size in validation errors
+    pub size_field: &'static str,
+    pub size: Option<u32>,
+    pub cursor: &'a Option<String>,
+    pub offset: Option<u32>,
+}
+
+impl Paging<'_> {
+    pub fn push_pairs(&self, pairs: &mut Vec<(&'static str, String)>) {
+        if let Some(limit) = self.size {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(cursor) = self.cursor {
+            pairs.push(("cursor", cursor.clone()));
+        }
+        if let Some(offset) = self.offset {
+            pairs.push(("offset", offset.to_string()));
+        }
+    }
+}
+
+impl Validate for Paging<'_> {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        if let Some(size) = self.size {
+            if size == 0 || size > MAX_PAGE_SIZE {
+                errors.push(field_path(path, self.size_field), format!("must be between 1 and {}", MAX_PAGE_SIZE));
+            }
+        }
+        if self.cursor.is_some() && self.offset.is_some() {
+            errors.push(field_path(path, "offset"), "cannot be combined with cursor");
+        }
+    }
+}
+
+/// A half-open time range filter, `[after, before)`.
+pub(crate) struct TimeRange {
+    /// Query parameter names
+    pub names: [&'static str; 2],
+    /// Field names in validation errors
+    pub fields: [&'static str; 2],
+    pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    pub fn push_pairs(&self, pairs: &mut Vec<(&'static str, String)>) {
+        let time = |t: DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::Secs, true);
+        if let Some(after) = self.after {
+            pairs.push((self.names[0], time(after)));
+        }
+        if let Some(before) = self.before {
+            pairs.push((self.names[1], time(before)));
+        }
+    }
+}
+
+impl Validate for TimeRange {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        if let (Some(after), Some(before)) = (self.after, self.before) {
+            if after >= before {
+                let [after_field, before_field] = self.fields;
+                errors.push(field_path(path, before_field), format!("must be later than {}", after_field));
+            }
+        }
+    }
+}
+
+/// One page of a list endpoint.
+///
+/// Endpoints that return a plain array are read as a single, final page.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    /// Items on this page
+    pub items: Vec<T>,
+    /// Cursor for the next page, if the endpoint pages by cursor
+    pub next_cursor: Option<String>,
+    /// Whether more items follow this page
+    pub has_more: Option<bool>,
+    /// Total number of matching items
+    pub total: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PageRepr<T> {
+    Items(Vec<T>),
+    Page(PageObject<T>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageObject<T> {
+    #[serde(alias = "data", alias = "jobs", alias = "schedules")]
+    items: Vec<T>,
+    next_cursor: Option<String>,
+    has_more: Option<bool>,
+    total: Option<i64>,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Page<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match PageRepr::deserialize(deserializer)? {
+            PageRepr::Items(items) => Page {
+                items,
+                next_cursor: None,
+                has_more: None,
+                total: None,
+            },
+            PageRepr::Page(page) => Page {
+                items: page.items,
+                next_cursor: page.next_cursor,
+                has_more: page.has_more,
+                total: page.total,
+            },
+        })
+    }
+}
+
+impl<T> Page<T> {
+    /// Options for fetching the page after this one, or `None` if this is the
+    /// last page.
+    ///
+    /// Follows `next_cursor` when present; otherwise advances the offset while
+    /// the API reports more items.
+    pub fn next_options(&self, current: &ListOptions) -> Option<ListOptions> {
+        next_page_options(current, self.items.len(), self.next_cursor.as_deref(), |offset| {
+            match (self.has_more, self.total) {
+                (Some(more), _) => more,
+                (None, Some(total)) => i64::from(offset) < total,
+                (None, None) => false,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_page_shapes() {
+        let page: Page<i32> = serde_json::from_str("[1, 2, 3]").unwrap();
+        assert_eq!(page.items, vec![1, 2, 3]);
+        assert!(page.next_options(&ListOptions::new()).is_none());
+
+        let page: Page<i32> = serde_json::from_str(r#"{"schedules": [1, 2], "total": 5}"#).unwrap();
+        let next = page.next_options(&ListOptions::new().page_size(2).offset(2)).unwrap();
+        assert_eq!((next.offset, next.page_size), (Some(4), Some(2)));
+
+        let page: Page<i32> = serde_json::from_str(r#"{"items": [1], "nextCursor": "c2"}"#).unwrap();
+        let next = page.next_options(&ListOptions::new().status("FAILED")).unwrap();
+        assert_eq!(next.cursor.as_deref(), Some("c2"));
+        assert_eq!(next.status.as_deref(), Some("FAILED"));
+
+        let page: Page<i32> = serde_json::from_str(r#"{"data": [1], "hasMore": false, "total": 10}"#).unwrap();
+        assert!(page.next_options(&ListOptions::new()).is_none());
+    }
+
+    #[test]
+    fn test_query_pairs_and_validation() {
+        let after = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let options = ListOptions::new()
+            .status("COMPLETED")
+            .created_after(after)
+            .url_contains("a&b")
+            .page_size(20)
+            .offset(40);
+        assert!(options.validate().is_ok());
+        assert_eq!(
+            options.query_pairs(),
+            vec![
+                ("status", "COMPLETED".to_string()),
+                ("created_after", "2024-01-01T00:00:00Z".to_string()),
+                ("url_contains", "a&b".to_string()),
+                ("limit", "20".to_string()),
+                ("offset", "40".to_string()),
+            ]
+        );
+
+        let errors = options.page_size(0).cursor("c1").created_before(after).violations();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.has_field("pageSize"));
+        assert!(errors.has_field("offset"));
+        assert!(errors.has_field("createdBefore"));
+    }
+}
@@ -1,10 +1,55 @@
 //! Schedule-related request and response models.
 
+use crate::error::AllscreenshotsError;
+use crate::validation::*;
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use super::common::*;
+use super::cron::CronSchedule;
+use super::pagination::{next_page_options, PageOptions, Paging, TimeRange};
+use super::timezone::Timezone;
+
+/// Maximum length of a schedule name, in characters.
+pub const MAX_SCHEDULE_NAME_LEN: usize = 255;
+
+fn check_name(errors: &mut ValidationErrors, path: &str, name: &str) {
+    if name.trim().is_empty() {
+        errors.push(field_path(path, "name"), "is required");
+    } else if name.chars().count() > MAX_SCHEDULE_NAME_LEN {
+        errors.push(
+            field_path(path, "name"),
+            format!("must be at most {} characters", MAX_SCHEDULE_NAME_LEN),
+        );
+    }
+}
+
+fn check_cron(errors: &mut ValidationErrors, path: &str, schedule: &str) {
+    if schedule.trim().is_empty() {
+        errors.push(field_path(path, "schedule"), "is required");
+    } else if let Err(AllscreenshotsError::ValidationError(message)) = CronSchedule::parse(schedule) {
+        errors.push(field_path(path, "schedule"), message);
+    }
+}
 
 /// Request to create a scheduled screenshot.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{CreateScheduleRequest, CronSchedule, Timezone};
+/// use chrono::{TimeZone, Utc};
+///
+/// let request = CreateScheduleRequest::builder()
+///     .name("Weekday capture")
+///     .url("https://github.com")
+///     .schedule(CronSchedule::weekdays_at(9, 0).unwrap())
+///     .timezone(Timezone::parse("Europe/Amsterdam").unwrap())
+///     .webhook_url("https://example.com/webhook")
+///     .starts_at(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateScheduleRequest {
     /// Schedule name (required, max 255 chars)
@@ -13,9 +58,9 @@ pub struct CreateScheduleRequest {
     pub url: String,
     /// Cron expression (required)
     pub schedule: String,
-    /// Timezone (e.g., "America/New_York")
+    /// Timezone (e.g., "America/New_York"); defaults to UTC
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub timezone: Option<String>,
+    pub timezone: Option<Timezone>,
     /// Screenshot options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<ScheduleScreenshotOptions>,
@@ -37,6 +82,11 @@ pub struct CreateScheduleRequest {
 }
 
 impl CreateScheduleRequest {
+    /// Create a new builder for CreateScheduleRequest.
+    pub fn builder() -> CreateScheduleRequestBuilder {
+        CreateScheduleRequestBuilder::default()
+    }
+
     /// Create a new schedule request.
     pub fn new(name: impl Into<String>, url: impl Into<String>, schedule: impl Into<String>) -> Self {
         Self {
@@ -54,8 +104,8 @@ impl CreateScheduleRequest {
     }
 
     /// Set the timezone.
-    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
-        self.timezone = Some(timezone.into());
+    pub fn with_timezone(mut self, timezone: Timezone) -> Self {
+        self.timezone = Some(timezone);
         self
     }
 
@@ -70,6 +120,116 @@ impl CreateScheduleRequest {
         self.retention_days = Some(days);
         self
     }
+
+    /// Set the webhook URL.
+    pub fn with_webhook(mut self, url: String, secret: Option<String>) -> Self {
+        self.webhook_url = Some(url);
+        self.webhook_secret = secret;
+        self
+    }
+
+    /// Preview the next `count` fire times in the schedule's timezone.
+    pub fn preview(&self, count: usize) -> Result<Vec<DateTime<chrono_tz::Tz>>, AllscreenshotsError> {
+        let timezone = self.timezone.unwrap_or_default();
+        Ok(CronSchedule::parse(&self.schedule)?.upcoming_in(&timezone, &Utc::now(), count))
+    }
+}
+
+/// Builder for CreateScheduleRequest.
+#[derive(Debug, Default)]
+pub struct CreateScheduleRequestBuilder {
+    request: CreateScheduleRequest,
+}
+
+impl CreateScheduleRequestBuilder {
+    /// Set the schedule name (required, max 255 chars).
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.request.name = name.into();
+        self
+    }
+
+    /// Set the target URL (required).
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.request.url = url.into();
+        self
+    }
+
+    /// Set the cron expression (required).
+    ///
+    /// Accepts a string or a [`CronSchedule`].
+    pub fn schedule(mut self, schedule: impl Into<String>) -> Self {
+        self.request.schedule = schedule.into();
+        self
+    }
+
+    /// Set the timezone.
+    pub fn timezone(mut self, timezone: Timezone) -> Self {
+        self.request.timezone = Some(timezone);
+        self
+    }
+
+    /// Set screenshot options.
+    pub fn options(mut self, options: ScheduleScreenshotOptions) -> Self {
+        self.request.options = Some(options);
+        self
+    }
+
+    /// Set the webhook URL.
+    pub fn webhook_url(mut self, url: impl Into<String>) -> Self {
+        self.request.webhook_url = Some(url.into());
+        self
+    }
+
+    /// Set the webhook secret.
+    pub fn webhook_secret(mut self, secret: impl Into<String>) -> Self {
+        self.request.webhook_secret = Some(secret.into());
+        self
+    }
+
+    /// Set the retention period in days (1-365).
+    pub fn retention_days(mut self, days: i32) -> Self {
+        self.request.retention_days = Some(days);
+        self
+    }
+
+    /// Set when the schedule starts.
+    pub fn starts_at(mut self, starts_at: DateTime<Utc>) -> Self {
+        self.request.starts_at = Some(starts_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        self
+    }
+
+    /// Set when the schedule ends.
+    pub fn ends_at(mut self, ends_at: DateTime<Utc>) -> Self {
+        self.request.ends_at = Some(ends_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        self
+    }
+
+    /// Build the request, validating all fields.
+    pub fn build(self) -> Result<CreateScheduleRequest, AllscreenshotsError> {
+        self.request.validate()?;
+        Ok(self.request)
+    }
+}
+
+fn check_window(errors: &mut ValidationErrors, path: &str, starts_at: Option<&str>, ends_at: Option<&str>) {
+    let parse = |s: Option<&str>| s.and_then(|s| DateTime::parse_from_rfc3339(s).ok());
+    if let (Some(starts_at), Some(ends_at)) = (parse(starts_at), parse(ends_at)) {
+        if ends_at <= starts_at {
+            errors.push(field_path(path, "endsAt"), "must be after startsAt");
+        }
+    }
+}
+
+impl Validate for CreateScheduleRequest {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_name(errors, path, &self.name);
+        check_http_url(errors, path, "url", &self.url);
+        check_cron(errors, path, &self.schedule);
+        self.options.collect_violations(&field_path(path, "options"), errors);
+        check_optional_http_url(errors, path, "webhookUrl", self.webhook_url.as_deref());
+        check_range(errors, path, "retentionDays", self.retention_days, 1..=365);
+        check_window(errors, path, self.starts_at.as_deref(), self.ends_at.as_deref());
+    }
 }
 
 /// Request to update a schedule.
@@ -87,7 +247,7 @@ pub struct UpdateScheduleRequest {
     pub schedule: Option<String>,
     /// Timezone
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub timezone: Option<String>,
+    pub timezone: Option<Timezone>,
     /// Screenshot options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<ScheduleScreenshotOptions>,
@@ -108,8 +268,42 @@ pub struct UpdateScheduleRequest {
     pub ends_at: Option<String>,
 }
 
+impl From<CreateScheduleRequest> for UpdateScheduleRequest {
+    /// An update that sets every field of `request`.
+    fn from(request: CreateScheduleRequest) -> Self {
+        Self {
+            name: Some(request.name),
+            url: Some(request.url),
+            schedule: Some(request.schedule),
+            timezone: request.timezone,
+            options: request.options,
+            webhook_url: request.webhook_url,
+            webhook_secret: request.webhook_secret,
+            retention_days: request.retention_days,
+            starts_at: request.starts_at,
+            ends_at: request.ends_at,
+        }
+    }
+}
+
+impl Validate for UpdateScheduleRequest {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        if let Some(name) = &self.name {
+            check_name(errors, path, name);
+        }
+        check_optional_http_url(errors, path, "url", self.url.as_deref());
+        if let Some(schedule) = &self.schedule {
+            check_cron(errors, path, schedule);
+        }
+        self.options.collect_violations(&field_path(path, "options"), errors);
+        check_optional_http_url(errors, path, "webhookUrl", self.webhook_url.as_deref());
+        check_range(errors, path, "retentionDays", self.retention_days, 1..=365);
+        check_window(errors, path, self.starts_at.as_deref(), self.ends_at.as_deref());
+    }
+}
+
 /// Screenshot options for scheduled captures.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleScreenshotOptions {
     /// Viewport configuration
@@ -157,6 +351,18 @@ pub struct ScheduleScreenshotOptions {
     /// Blocking level
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_level: Option<BlockLevel>,
+    /// Options not covered by the fields above, sent and returned as-is
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Validate for ScheduleScreenshotOptions {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        self.viewport.collect_violations(&field_path(path, "viewport"), errors);
+        check_range(errors, path, "quality", self.quality, 1..=100);
+        check_range(errors, path, "delay", self.delay, 0..=30000);
+        check_range(errors, path, "timeout", self.timeout, 1000..=60000);
+    }
 }
 
 /// Response for a schedule.
@@ -178,7 +384,7 @@ pub struct ScheduleResponse {
     /// Current status
     pub status: String,
     /// Screenshot options
-    pub options: Option<serde_json::Value>,
+    pub options: Option<ScheduleScreenshotOptions>,
     /// Webhook URL
     pub webhook_url: Option<String>,
     /// Retention period in days
@@ -203,6 +409,56 @@ pub struct ScheduleResponse {
     pub updated_at: Option<String>,
 }
 
+impl ScheduleResponse {
+    /// A request that recreates this schedule, e.g. in another account.
+    ///
+    /// The webhook secret is never returned by the API, so it is not included.
+    pub fn to_create_request(&self) -> Result<CreateScheduleRequest, AllscreenshotsError> {
+        Ok(CreateScheduleRequest {
+            name: self.name.clone(),
+            url: self.url.clone(),
+            schedule: self.schedule.clone(),
+            timezone: self.timezone.as_deref().map(Timezone::parse).transpose()?,
+            options: self.options.clone(),
+            webhook_url: self.webhook_url.clone(),
+            webhook_secret: None,
+            retention_days: self.retention_days,
+            starts_at: self.starts_at.clone(),
+            ends_at: self.ends_at.clone(),
+        })
+    }
+
+    /// Parse the schedule's cron expression.
+    pub fn cron(&self) -> Result<CronSchedule, AllscreenshotsError> {
+        CronSchedule::parse(&self.schedule)
+    }
+
+    /// Parse `next_execution_at` as reported by the API.
+    pub fn next_execution(&self) -> Option<DateTime<Utc>> {
+        self.next_execution_at
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Parse the schedule's timezone, defaulting to UTC when unset.
+    pub fn parsed_timezone(&self) -> Result<Timezone, AllscreenshotsError> {
+        self.timezone.as_deref().map(Timezone::parse).unwrap_or(Ok(Timezone::UTC))
+    }
+
+    /// Compute the next `count` fire times after `after` in the schedule's timezone.
+    ///
+    /// Comparing the first entry against [`next_execution`](Self::next_execution)
+    /// shows whether the API interprets the schedule the same way.
+    pub fn upcoming_executions(
+        &self,
+        after: &DateTime<Utc>,
+        count: usize,
+    ) -> Result<Vec<DateTime<chrono_tz::Tz>>, AllscreenshotsError> {
+        Ok(self.cron()?.upcoming_in(&self.parsed_timezone()?, after, count))
+    }
+}
+
 /// Response for listing schedules.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -211,6 +467,8 @@ pub struct ScheduleListResponse {
     pub schedules: Vec<ScheduleResponse>,
     /// Total count
     pub total: i32,
+    /// Cursor for the next page, if there is one
+    pub next_cursor: Option<String>,
 }
 
 /// Response for schedule execution history.
@@ -223,6 +481,134 @@ pub struct ScheduleHistoryResponse {
     pub total_executions: i64,
     /// List of executions
     pub executions: Vec<ScheduleExecutionResponse>,
+    /// Cursor for the next page, if there is one
+    pub next_cursor: Option<String>,
+}
+
+impl ScheduleHistoryResponse {
+    /// Options for fetching the page after this one, or `None` if this is the
+    /// last page.
+    ///
+    /// Follows `next_cursor` when present; otherwise advances the offset until
+    /// `total_executions` is reached.
+    pub fn next_options(&self, current: &ScheduleHistoryOptions) -> Option<ScheduleHistoryOptions> {
+        next_page_options(current, self.executions.len(), self.next_cursor.as_deref(), |offset| {
+            i64::from(offset) < self.total_executions
+        })
+    }
+}
+
+/// Filters and paging for schedule execution history.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::ScheduleHistoryOptions;
+/// use chrono::{Duration, Utc};
+///
+/// let options = ScheduleHistoryOptions::new()
+///     .status("COMPLETED")
+///     .executed_after(Utc::now() - Duration::days(30))
+///     .limit(100);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleHistoryOptions {
+    /// Only include executions with this status
+    pub status: Option<String>,
+    /// Only include executions at or after this time
+    pub executed_after: Option<DateTime<Utc>>,
+    /// Only include executions before this time
+    pub executed_before: Option<DateTime<Utc>>,
+    /// Number of executions per page (1-100)
+    pub limit: Option<u32>,
+    /// Cursor returned with the previous page
+    pub cursor: Option<String>,
+    /// Number of executions to skip
+    pub offset: Option<u32>,
+}
+
+impl ScheduleHistoryOptions {
+    /// Create options without any filters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only include executions with this status, e.g. `"FAILED"`.
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    /// Only include executions at or after this time.
+    pub fn executed_after(mut self, time: DateTime<Utc>) -> Self {
+        self.executed_after = Some(time);
+        self
+    }
+
+    /// Only include executions before this time.
+    pub fn executed_before(mut self, time: DateTime<Utc>) -> Self {
+        self.executed_before = Some(time);
+        self
+    }
+
+    /// Set the number of executions per page.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Continue from the cursor returned with a previous page.
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Skip this many executions.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Query parameters for the history endpoint, unencoded.
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(status) = &self.status {
+            pairs.push(("status", status.clone()));
+        }
+        self.executed_range().push_pairs(&mut pairs);
+        self.paging().push_pairs(&mut pairs);
+        pairs
+    }
+
+    fn executed_range(&self) -> TimeRange {
+        TimeRange {
+            names: ["executed_after", "executed_before"],
+            fields: ["executedAfter", "executedBefore"],
+            after: self.executed_after,
+            before: self.executed_before,
+        }
+    }
+
+    fn paging(&self) -> Paging<'_> {
+        Paging { size_field: "limit", size: self.limit, cursor: &self.cursor, offset: self.offset }
+    }
+}
+
+impl Validate for ScheduleHistoryOptions {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        self.paging().collect_violations(path, errors);
+        self.executed_range().collect_violations(path, errors);
+    }
+}
+
+impl PageOptions for ScheduleHistoryOptions {
+    fn offset(&self) -> Option<u32> {
+        self.offset
+    }
+
+    fn with_position(&self, cursor: Option<String>, offset: Option<u32>) -> Self {
+        Self { cursor, offset, ..self.clone() }
+    }
 }
 
 /// Response for a single schedule execution.
@@ -250,3 +636,211 @@ pub struct ScheduleExecutionResponse {
     /// Expiration timestamp
     pub expires_at: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_valid_request() {
+        let request = CreateScheduleRequest::new("Daily", "https://example.com", "0 9 * * *")
+            .with_retention_days(30);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder() {
+        use chrono::TimeZone;
+
+        let request = CreateScheduleRequest::builder()
+            .name("Weekday capture")
+            .url("https://example.com")
+            .schedule(CronSchedule::weekdays_at(9, 0).unwrap())
+            .timezone(Timezone::parse("Europe/Amsterdam").unwrap())
+            .webhook_url("https://example.com/webhook")
+            .webhook_secret("secret")
+            .retention_days(7)
+            .starts_at(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+            .ends_at(Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(request.schedule, "0 9 * * 1-5");
+        assert_eq!(request.webhook_url.as_deref(), Some("https://example.com/webhook"));
+        assert_eq!(request.starts_at.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(request.ends_at.as_deref(), Some("2025-12-31T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_builder_validates() {
+        use chrono::TimeZone;
+
+        assert!(CreateScheduleRequest::builder().build().is_err());
+
+        let result = CreateScheduleRequest::builder()
+            .name("Backwards")
+            .url("https://example.com")
+            .schedule("0 9 * * *")
+            .starts_at(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap())
+            .ends_at(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+            .build();
+        match result {
+            Err(AllscreenshotsError::InvalidRequest(errors)) => assert!(errors.has_field("endsAt")),
+            other => panic!("Expected InvalidRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_rejects_unknown_timezone() {
+        let json = r#"{"name": "Daily", "url": "https://example.com", "schedule": "0 9 * * *", "timezone": "EST5EDT "}"#;
+        let err = serde_json::from_str::<CreateScheduleRequest>(json).unwrap_err();
+        assert!(err.to_string().contains("Unknown timezone 'EST5EDT '"));
+    }
+
+    #[test]
+    fn test_validate_create_request() {
+        let request = CreateScheduleRequest::new("x".repeat(256), "https://example.com", " ")
+            .with_retention_days(366)
+            .with_options(ScheduleScreenshotOptions {
+                viewport: Some(ViewportConfig::new(99, 1080)),
+                ..Default::default()
+            });
+
+        let errors = request.violations();
+        assert_eq!(errors.len(), 4);
+        assert!(errors.has_field("name"));
+        assert!(errors.has_field("schedule"));
+        assert!(errors.has_field("retentionDays"));
+        assert!(errors.has_field("options.viewport.width"));
+    }
+
+    #[test]
+    fn test_validate_invalid_cron() {
+        let request = CreateScheduleRequest::new("Daily", "https://example.com", "0 25 * * *");
+        let errors = request.violations();
+        assert_eq!(errors.len(), 1);
+        assert!(errors.has_field("schedule"));
+    }
+
+    #[test]
+    fn test_upcoming_executions_match_api() {
+        let json = r#"{
+            "id": "sched-1",
+            "name": "Daily",
+            "url": "https://example.com",
+            "schedule": "0 9 * * *",
+            "timezone": "Europe/Amsterdam",
+            "status": "ACTIVE",
+            "nextExecutionAt": "2024-06-02T07:00:00Z"
+        }"#;
+        let schedule: ScheduleResponse = serde_json::from_str(json).unwrap();
+
+        let after = DateTime::parse_from_rfc3339("2024-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let runs = schedule.upcoming_executions(&after, 1).unwrap();
+        assert_eq!(Some(runs[0].with_timezone(&Utc)), schedule.next_execution());
+    }
+
+    #[test]
+    fn test_validate_update_request_only_checks_set_fields() {
+        assert!(UpdateScheduleRequest::default().validate().is_ok());
+
+        let request = UpdateScheduleRequest {
+            name: Some(String::new()),
+            retention_days: Some(0),
+            ..Default::default()
+        };
+        let errors = request.violations();
+        assert!(errors.has_field("name"));
+        assert!(errors.has_field("retentionDays"));
+    }
+
+    #[test]
+    fn test_history_next_options() {
+        let execution = |id: &str| ScheduleExecutionResponse {
+            id: id.to_string(),
+            executed_at: "2024-01-15T09:00:00Z".to_string(),
+            status: "COMPLETED".to_string(),
+            result_url: None,
+            storage_url: None,
+            file_size: None,
+            render_time_ms: None,
+            error_code: None,
+            error_message: None,
+            expires_at: None,
+        };
+        let mut page = ScheduleHistoryResponse {
+            schedule_id: "s1".to_string(),
+            total_executions: 3,
+            executions: vec![execution("e1"), execution("e2")],
+            next_cursor: None,
+        };
+        let options = ScheduleHistoryOptions::new().status("COMPLETED").limit(2);
+        let next = page.next_options(&options).unwrap();
+        assert_eq!((next.offset, next.limit, next.status.as_deref()), (Some(2), Some(2), Some("COMPLETED")));
+        assert!(page.next_options(&next).is_none());
+
+        page.next_cursor = Some("c2".to_string());
+        let next = page.next_options(&next).unwrap();
+        assert_eq!((next.cursor.as_deref(), next.offset), (Some("c2"), None));
+
+        let errors = options.offset(4).cursor("c1").limit(101).violations();
+        assert!(errors.has_field("limit"));
+        assert!(errors.has_field("offset"));
+    }
+
+    #[test]
+    fn test_response_options_round_trip() {
+        let written = ScheduleScreenshotOptions {
+            device: Some("iPhone 14".to_string()),
+            full_page: Some(true),
+            block_level: Some(BlockLevel::Normal),
+            extra: ExtraFields::from([("geolocation".to_string(), serde_json::json!({"country": "NL"}))]),
+            ..Default::default()
+        };
+        let sent = serde_json::to_value(&written).unwrap();
+        assert_eq!(sent["geolocation"]["country"], "NL");
+
+        let response: ScheduleResponse = serde_json::from_value(serde_json::json!({
+            "id": "s1",
+            "name": "Daily",
+            "url": "https://example.com",
+            "schedule": "0 9 * * *",
+            "status": "ACTIVE",
+            "options": sent
+        }))
+        .unwrap();
+        assert_eq!(response.options, Some(written.clone()));
+        assert_eq!(response.to_create_request().unwrap().options, Some(written));
+    }
+
+    #[test]
+    fn test_unknown_values_round_trip_through_export() {
+        let response: ScheduleResponse = serde_json::from_value(serde_json::json!({
+            "id": "s1",
+            "name": "Daily",
+            "url": "https://example.com",
+            "schedule": "0 9 * * *",
+            "status": "ACTIVE",
+            "options": {"format": "avif", "waitUntil": "networkidle0", "blockLevel": "paranoid", "fullPage": true}
+        }))
+        .unwrap();
+        let options = response.options.clone().unwrap();
+        assert_eq!(options.format, Some(ImageFormat::Unknown("avif".to_string())));
+        assert_eq!(options.wait_until, Some(WaitUntil::Unknown("networkidle0".to_string())));
+        assert_eq!(options.block_level, Some(BlockLevel::Unknown("paranoid".to_string())));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schedules.json");
+        crate::schedules::ScheduleExport::new(vec![response]).save(&path).unwrap();
+        let loaded = crate::schedules::ScheduleExport::load(&path).unwrap();
+
+        let request = loaded.schedules[0].to_create_request().unwrap();
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["options"]["format"], "avif");
+        assert_eq!(body["options"]["waitUntil"], "networkidle0");
+        assert_eq!(body["options"]["blockLevel"], "paranoid");
+        assert_eq!(body["options"]["fullPage"], true);
+    }
+}
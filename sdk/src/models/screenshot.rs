@@ -1,7 +1,9 @@
 //! Screenshot-related request and response models.
 
 use crate::error::AllscreenshotsError;
+use crate::validation::*;
 use serde::{Deserialize, Serialize};
+use super::bulk::BulkDefaults;
 use super::common::*;
 
 /// Request to take a screenshot.
@@ -96,6 +98,45 @@ impl ScreenshotRequest {
             ..Default::default()
         }
     }
+
+    /// Fill options the request leaves unset from `defaults`, as
+    /// [`BulkDefaults::fill_unset_from`] does.
+    pub fn fill_unset_from(self, defaults: &BulkDefaults) -> Self {
+        let own = BulkDefaults {
+            viewport: self.viewport,
+            device: self.device,
+            format: self.format,
+            full_page: self.full_page,
+            quality: self.quality,
+            delay: self.delay,
+            wait_for: self.wait_for,
+            wait_until: self.wait_until,
+            timeout: self.timeout,
+            dark_mode: self.dark_mode,
+            custom_css: self.custom_css,
+            block_ads: self.block_ads,
+            block_cookie_banners: self.block_cookie_banners,
+            block_level: self.block_level,
+        };
+        let merged = own.fill_unset_from(defaults);
+        Self {
+            viewport: merged.viewport,
+            device: merged.device,
+            format: merged.format,
+            full_page: merged.full_page,
+            quality: merged.quality,
+            delay: merged.delay,
+            wait_for: merged.wait_for,
+            wait_until: merged.wait_until,
+            timeout: merged.timeout,
+            dark_mode: merged.dark_mode,
+            custom_css: merged.custom_css,
+            block_ads: merged.block_ads,
+            block_cookie_banners: merged.block_cookie_banners,
+            block_level: merged.block_level,
+            ..self
+        }
+    }
 }
 
 /// Builder for ScreenshotRequest.
@@ -225,52 +266,24 @@ impl ScreenshotRequestBuilder {
         self
     }
 
-    /// Build the request, validating required fields.
+    /// Build the request, validating all fields.
     pub fn build(self) -> Result<ScreenshotRequest, AllscreenshotsError> {
-        if self.request.url.is_empty() {
-            return Err(AllscreenshotsError::ValidationError(
-                "URL is required".to_string(),
-            ));
-        }
-
-        // Validate URL format
-        if !self.request.url.starts_with("http://") && !self.request.url.starts_with("https://") {
-            return Err(AllscreenshotsError::ValidationError(
-                "URL must start with http:// or https://".to_string(),
-            ));
-        }
-
-        // Validate quality if set
-        if let Some(quality) = self.request.quality {
-            if !(1..=100).contains(&quality) {
-                return Err(AllscreenshotsError::ValidationError(
-                    "Quality must be between 1 and 100".to_string(),
-                ));
-            }
-        }
-
-        // Validate delay if set
-        if let Some(delay) = self.request.delay {
-            if !(0..=30000).contains(&delay) {
-                return Err(AllscreenshotsError::ValidationError(
-                    "Delay must be between 0 and 30000 milliseconds".to_string(),
-                ));
-            }
-        }
-
-        // Validate timeout if set
-        if let Some(timeout) = self.request.timeout {
-            if !(1000..=60000).contains(&timeout) {
-                return Err(AllscreenshotsError::ValidationError(
-                    "Timeout must be between 1000 and 60000 milliseconds".to_string(),
-                ));
-            }
-        }
-
+        self.request.validate()?;
         Ok(self.request)
     }
 }
 
+impl Validate for ScreenshotRequest {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        check_http_url(errors, path, "url", &self.url);
+        self.viewport.collect_violations(&field_path(path, "viewport"), errors);
+        check_range(errors, path, "quality", self.quality, 1..=100);
+        check_range(errors, path, "delay", self.delay, 0..=30000);
+        check_range(errors, path, "timeout", self.timeout, 1000..=60000);
+        check_optional_http_url(errors, path, "webhookUrl", self.webhook_url.as_deref());
+    }
+}
+
 /// Response for an async screenshot job creation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -310,7 +323,34 @@ pub struct JobResponse {
     /// Expiration timestamp
     pub expires_at: Option<String>,
     /// Additional metadata
-    pub metadata: Option<serde_json::Value>,
+    pub metadata: Option<JobMetadata>,
+}
+
+/// Metadata of a screenshot job's result.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobMetadata {
+    /// Image width
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    /// Image height
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    /// Output format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// File size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<i64>,
+    /// Render time in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub render_time_ms: Option<i64>,
+    /// Device preset used for the capture
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Any other metadata returned by the API
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[cfg(test)]
@@ -355,9 +395,70 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_builder_reports_all_violations() {
+        let result = ScreenshotRequest::builder()
+            .url("example.com")
+            .quality(0)
+            .delay(-1)
+            .viewport(ViewportConfig::new(50, 5000))
+            .build();
+
+        match result {
+            Err(AllscreenshotsError::InvalidRequest(errors)) => {
+                assert_eq!(errors.len(), 5);
+                assert!(errors.has_field("url"));
+                assert!(errors.has_field("quality"));
+                assert!(errors.has_field("delay"));
+                assert!(errors.has_field("viewport.width"));
+                assert!(errors.has_field("viewport.height"));
+            }
+            other => panic!("Expected InvalidRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fill_unset_from_defaults() {
+        let defaults = BulkDefaults {
+            device: Some("Desktop HD".to_string()),
+            format: Some(ImageFormat::Jpeg),
+            full_page: Some(true),
+            ..Default::default()
+        };
+
+        let request = ScreenshotRequest::simple("https://example.com").fill_unset_from(&defaults);
+        assert_eq!(request.device.as_deref(), Some("Desktop HD"));
+        assert_eq!(request.format, Some(ImageFormat::Jpeg));
+
+        // A viewport of its own keeps the default device out, other options are still filled
+        let viewport = ViewportConfig { width: Some(800), height: Some(600), ..Default::default() };
+        let mut request = ScreenshotRequest::simple("https://example.com");
+        request.viewport = Some(viewport.clone());
+        request.full_page = Some(false);
+        let request = request.fill_unset_from(&defaults);
+        assert_eq!(request.device, None);
+        assert_eq!(request.viewport, Some(viewport));
+        assert_eq!((request.format, request.full_page), (Some(ImageFormat::Jpeg), Some(false)));
+        assert_eq!(request.url, "https://example.com");
+    }
+
     #[test]
     fn test_simple_request() {
         let request = ScreenshotRequest::simple("https://example.com");
         assert_eq!(request.url, "https://example.com");
     }
+
+    #[test]
+    fn test_job_metadata_keeps_unknown_fields() {
+        let job: JobResponse = serde_json::from_value(serde_json::json!({
+            "id": "job-1",
+            "status": "COMPLETED",
+            "metadata": {"width": 1920, "height": 1080, "fileSize": 2048, "pageTitle": "Example"}
+        }))
+        .unwrap();
+        let metadata = job.metadata.unwrap();
+        assert_eq!((metadata.width, metadata.file_size), (Some(1920), Some(2048)));
+        assert_eq!(metadata.extra["pageTitle"], "Example");
+        assert_eq!(serde_json::to_value(&metadata).unwrap()["pageTitle"], "Example");
+    }
 }
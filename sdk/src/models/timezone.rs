@@ -0,0 +1,153 @@
+//! IANA timezones for scheduled screenshots.
+
+use crate::error::AllscreenshotsError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A validated IANA timezone (e.g. `America/New_York`), backed by the tz database.
+///
+/// Names are matched exactly, so typos and stray whitespace are rejected
+/// instead of silently falling back to UTC on the server.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::Timezone;
+///
+/// let tz: Timezone = "Europe/Amsterdam".parse().unwrap();
+/// assert_eq!(tz.name(), "Europe/Amsterdam");
+///
+/// let err = Timezone::parse("europe/amsterdam").unwrap_err();
+/// assert!(err.to_string().contains("did you mean 'Europe/Amsterdam'"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Timezone(chrono_tz::Tz);
+
+impl Timezone {
+    /// Coordinated Universal Time, the API's default.
+    pub const UTC: Timezone = Timezone(chrono_tz::UTC);
+
+    /// Parse an IANA timezone name.
+    pub fn parse(name: &str) -> Result<Self, AllscreenshotsError> {
+        if let Ok(tz) = chrono_tz::Tz::from_str(name) {
+            return Ok(Self(tz));
+        }
+
+        let trimmed = name.trim();
+        let reason = if trimmed != name && chrono_tz::Tz::from_str(trimmed).is_ok() {
+            format!("remove the surrounding whitespace to use '{}'", trimmed)
+        } else if let Some(tz) = chrono_tz::TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(trimmed))
+        {
+            format!("did you mean '{}'?", tz.name())
+        } else {
+            "not found in the tz database".to_string()
+        };
+
+        Err(AllscreenshotsError::ValidationError(format!(
+            "Unknown timezone '{}': {}",
+            name, reason
+        )))
+    }
+
+    /// The canonical IANA name.
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    /// The underlying `chrono-tz` timezone, for date arithmetic.
+    pub fn tz(&self) -> chrono_tz::Tz {
+        self.0
+    }
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Self::UTC
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = AllscreenshotsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Timezone {
+    type Error = AllscreenshotsError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl TryFrom<&str> for Timezone {
+    type Error = AllscreenshotsError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<chrono_tz::Tz> for Timezone {
+    fn from(tz: chrono_tz::Tz) -> Self {
+        Self(tz)
+    }
+}
+
+impl From<Timezone> for String {
+    fn from(tz: Timezone) -> Self {
+        tz.name().to_string()
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid() {
+        assert_eq!(Timezone::parse("America/New_York").unwrap().name(), "America/New_York");
+        assert_eq!(Timezone::parse("EST5EDT").unwrap().name(), "EST5EDT");
+        assert_eq!(Timezone::parse("UTC").unwrap(), Timezone::UTC);
+    }
+
+    #[test]
+    fn test_trailing_whitespace_rejected() {
+        let err = Timezone::parse("EST5EDT ").unwrap_err();
+        assert!(err.to_string().contains("'EST5EDT '"));
+        assert!(err.to_string().contains("whitespace"));
+    }
+
+    #[test]
+    fn test_wrong_case_suggests_name() {
+        let err = Timezone::parse("america/new_york").unwrap_err();
+        assert!(err.to_string().contains("did you mean 'America/New_York'?"));
+    }
+
+    #[test]
+    fn test_unknown_zone() {
+        let err = Timezone::parse("Mars/Olympus_Mons").unwrap_err();
+        assert!(matches!(err, AllscreenshotsError::ValidationError(_)));
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_serde() {
+        let tz = Timezone::parse("Asia/Tokyo").unwrap();
+        assert_eq!(serde_json::to_string(&tz).unwrap(), "\"Asia/Tokyo\"");
+        assert_eq!(serde_json::from_str::<Timezone>("\"Asia/Tokyo\"").unwrap(), tz);
+        assert!(serde_json::from_str::<Timezone>("\"Asia/Tokio\"").is_err());
+    }
+}
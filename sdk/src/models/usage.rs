@@ -1,6 +1,12 @@
 //! Usage and quota-related response models.
 
+use crate::error::AllscreenshotsError;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+const CSV_HEADER: &str =
+    "period_start,period_end,screenshots_count,bandwidth_bytes,bytes_per_screenshot,screenshots_change,bandwidth_change";
 
 /// Response for usage statistics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,3 +111,236 @@ pub struct QuotaResponse {
     /// Monthly bandwidth limit formatted
     pub monthly_bandwidth_formatted: Option<String>,
 }
+
+impl UsageResponse {
+    /// Screenshots expected by the end of the current period if usage
+    /// continues at the rate seen so far.
+    pub fn projected_screenshots(&self, now: DateTime<Utc>) -> Option<i64> {
+        let (elapsed, length) = self.current_period.progress(now)?;
+        let count = self.current_period.screenshots_count as f64;
+        Some((count * length / elapsed).round() as i64)
+    }
+
+    /// Average result size in bytes, over all time when totals are available
+    /// and over the current period otherwise.
+    pub fn average_bytes_per_screenshot(&self) -> Option<f64> {
+        match &self.totals {
+            Some(totals) if totals.screenshots_count > 0 => {
+                Some(totals.bandwidth_bytes as f64 / totals.screenshots_count as f64)
+            }
+            _ => self.current_period.average_bytes_per_screenshot(),
+        }
+    }
+
+    /// Usage of every known period, oldest first, with the change from the
+    /// period before. The last entry is the current, still running period.
+    pub fn period_changes(&self) -> Vec<PeriodChange> {
+        let mut periods: Vec<&PeriodUsageResponse> = self.history.iter().flatten().collect();
+        if !periods.iter().any(|p| p.period_start == self.current_period.period_start) {
+            periods.push(&self.current_period);
+        }
+        periods.sort_by_key(|p| p.starts_at());
+
+        let change = |current: f64, previous: f64| (previous > 0.0).then(|| (current - previous) / previous);
+        let mut changes: Vec<PeriodChange> = Vec::with_capacity(periods.len());
+        for period in periods {
+            let previous = changes.last().map(|c| &c.period);
+            changes.push(PeriodChange {
+                screenshots_change: previous.and_then(|p| {
+                    change(period.screenshots_count as f64, p.screenshots_count as f64)
+                }),
+                bandwidth_change: previous.and_then(|p| change(period.bandwidth_bytes as f64, p.bandwidth_bytes as f64)),
+                period: period.clone(),
+            });
+        }
+        changes
+    }
+
+    /// When the screenshot or bandwidth quota runs out at the current rate,
+    /// whichever comes first. `None` if it lasts until the end of the period.
+    pub fn quota_exhausted_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let quota = self.quota.as_ref()?;
+        let start = self.current_period.starts_at()?;
+        let (elapsed, length) = self.current_period.progress(now)?;
+
+        let exhausted_at = |used: f64, limit: f64| {
+            // Compare rates first: a quota that lasts past the end of the
+            // period can be far enough out to overflow a timestamp
+            if used <= 0.0 || limit / used >= length / elapsed {
+                return None;
+            }
+            let lasts = Duration::try_milliseconds((elapsed * 1000.0 * limit / used) as i64)?;
+            start.checked_add_signed(lasts)
+        };
+        let screenshots = exhausted_at(self.current_period.screenshots_count as f64, quota.monthly_limit as f64);
+        let bandwidth = quota
+            .monthly_bandwidth_bytes
+            .and_then(|limit| exhausted_at(self.current_period.bandwidth_bytes as f64, limit as f64));
+        screenshots.into_iter().chain(bandwidth).min()
+    }
+
+    /// Write [`period_changes`](Self::period_changes) as CSV with a header row.
+    ///
+    /// Changes are relative, e.g. `0.25` for 25% more than the period before,
+    /// and left empty where there is nothing to compare with.
+    pub fn write_history_csv<W: Write>(&self, mut writer: W) -> Result<(), AllscreenshotsError> {
+        let optional = |value: Option<f64>| value.map(|v| format!("{:.4}", v)).unwrap_or_default();
+        writeln!(writer, "{}", CSV_HEADER)?;
+        for change in self.period_changes() {
+            let period = &change.period;
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                csv_field(&period.period_start),
+                csv_field(&period.period_end),
+                period.screenshots_count,
+                period.bandwidth_bytes,
+                period.average_bytes_per_screenshot().map(|v| format!("{:.0}", v)).unwrap_or_default(),
+                optional(change.screenshots_change),
+                optional(change.bandwidth_change),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The history as a CSV string; see [`write_history_csv`](Self::write_history_csv).
+    pub fn history_csv(&self) -> String {
+        let mut csv = Vec::new();
+        self.write_history_csv(&mut csv).expect("writing to a Vec cannot fail");
+        String::from_utf8(csv).expect("CSV is built from strings")
+    }
+}
+
+impl PeriodUsageResponse {
+    /// Start of the period. Date-only values start at midnight UTC.
+    pub fn starts_at(&self) -> Option<DateTime<Utc>> {
+        parse_period_bound(&self.period_start, false)
+    }
+
+    /// End of the period. Date-only values include the whole day.
+    pub fn ends_at(&self) -> Option<DateTime<Utc>> {
+        parse_period_bound(&self.period_end, true)
+    }
+
+    /// Average result size in bytes, `None` if no screenshots were taken.
+    pub fn average_bytes_per_screenshot(&self) -> Option<f64> {
+        (self.screenshots_count > 0).then(|| self.bandwidth_bytes as f64 / self.screenshots_count as f64)
+    }
+
+    /// Elapsed and total length of the period in seconds, with `now`
+    /// clamped to the period.
+    fn progress(&self, now: DateTime<Utc>) -> Option<(f64, f64)> {
+        let start = self.starts_at()?;
+        let end = self.ends_at()?;
+        let elapsed = (now.min(end) - start).num_milliseconds() as f64 / 1000.0;
+        let length = (end - start).num_milliseconds() as f64 / 1000.0;
+        (elapsed > 0.0 && length > 0.0).then_some((elapsed, length))
+    }
+}
+
+/// Usage of one period compared with the period before.
+#[derive(Debug, Clone)]
+pub struct PeriodChange {
+    /// The period
+    pub period: PeriodUsageResponse,
+    /// Relative change in screenshots, e.g. `0.25` for 25% more
+    pub screenshots_change: Option<f64>,
+    /// Relative change in bandwidth
+    pub bandwidth_change: Option<f64>,
+}
+
+fn parse_period_bound(value: &str, end: bool) -> Option<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let date = if end { date.succ_opt()? } else { date };
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn usage() -> UsageResponse {
+        serde_json::from_value(json!({
+            "tier": "PRO",
+            "currentPeriod": {
+                "periodStart": "2024-03-01", "periodEnd": "2024-03-10",
+                "screenshotsCount": 300, "bandwidthBytes": 3_000_000, "bandwidthFormatted": "3 MB"
+            },
+            "quota": {"monthlyLimit": 1000, "monthlyBandwidthBytes": 8_000_000},
+            "history": [
+                {
+                    "periodStart": "2024-02-01", "periodEnd": "2024-02-29",
+                    "screenshotsCount": 400, "bandwidthBytes": 2_000_000, "bandwidthFormatted": "2 MB"
+                },
+                {
+                    "periodStart": "2024-01-01", "periodEnd": "2024-01-31",
+                    "screenshotsCount": 0, "bandwidthBytes": 0, "bandwidthFormatted": "0 B"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_run_rate() {
+        let usage = usage();
+        // A ten day period, three days in
+        let now = at("2024-03-04T00:00:00Z");
+        assert_eq!(usage.projected_screenshots(now), Some(1000));
+        assert_eq!(usage.average_bytes_per_screenshot(), Some(10_000.0));
+        // Bandwidth (8 MB at 1 MB a day) runs out before the 1000 screenshots do
+        assert_eq!(usage.quota_exhausted_at(now), Some(at("2024-03-09T00:00:00Z")));
+        assert_eq!(usage.quota_exhausted_at(at("2024-03-08T00:00:00Z")), None);
+        assert_eq!(usage.projected_screenshots(at("2024-02-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn test_tiny_usage_against_large_limit() {
+        let usage: UsageResponse = serde_json::from_value(json!({
+            "tier": "PRO",
+            "currentPeriod": {
+                "periodStart": "2024-03-01", "periodEnd": "2024-03-31",
+                "screenshotsCount": 1, "bandwidthBytes": 100, "bandwidthFormatted": "100 B"
+            },
+            "quota": {"monthlyLimit": 1000, "monthlyBandwidthBytes": 100_000_000_000u64}
+        }))
+        .unwrap();
+        assert_eq!(usage.quota_exhausted_at(at("2024-03-11T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn test_history_csv() {
+        let changes = usage().period_changes();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[1].screenshots_change, None);
+        assert_eq!(changes[2].screenshots_change, Some(-0.25));
+        assert_eq!(changes[2].bandwidth_change, Some(0.5));
+
+        assert_eq!(
+            usage().history_csv(),
+            format!(
+                "{}\n{}\n{}\n{}\n",
+                CSV_HEADER,
+                "2024-01-01,2024-01-31,0,0,,,",
+                "2024-02-01,2024-02-29,400,2000000,5000,,",
+                "2024-03-01,2024-03-10,300,3000000,10000,-0.2500,0.5000"
+            )
+        );
+    }
+}
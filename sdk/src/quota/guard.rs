@@ -0,0 +1,322 @@
+//! Client-side quota preflight and hourly budget.
+
+use crate::error::AllscreenshotsError;
+use crate::models::QuotaStatusResponse;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+const BUDGET_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Optional guard that refuses requests before they would exhaust the quota.
+///
+/// The guard caches [`get_quota`](crate::AllscreenshotsClient::get_quota) for
+/// `refresh_interval` and counts screenshots and downloaded bytes locally in
+/// between. Screenshots are reserved before a request is sent and given back
+/// if it fails. Requests that would leave less than the configured reserve, or
+/// exceed the per-process hourly budget, fail with
+/// [`AllscreenshotsError::QuotaExceeded`] without reaching the API.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use allscreenshots_sdk::{AllscreenshotsClient, QuotaGuard};
+/// use std::time::Duration;
+///
+/// let client = AllscreenshotsClient::builder()
+///     .api_key("your-api-key")
+///     .quota_guard(
+///         QuotaGuard::default()
+///             .refresh_interval(Duration::from_secs(60))
+///             .min_remaining_screenshots(100)
+///             .max_screenshots_per_hour(500),
+///     )
+///     .build()?;
+/// # Ok::<(), allscreenshots_sdk::AllscreenshotsError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuotaGuard {
+    /// How long a quota snapshot is trusted before it is fetched again
+    pub refresh_interval: Duration,
+    /// Screenshots that must remain in the quota after a request
+    pub min_remaining_screenshots: i64,
+    /// Bandwidth in bytes that must remain in the quota
+    pub min_remaining_bytes: i64,
+    /// Maximum screenshots this process may take per hour
+    pub max_screenshots_per_hour: Option<u64>,
+    /// Maximum bytes this process may download per hour
+    pub max_bytes_per_hour: Option<u64>,
+}
+
+impl Default for QuotaGuard {
+    fn default() -> Self {
+        Self {
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            min_remaining_screenshots: 0,
+            min_remaining_bytes: 0,
+            max_screenshots_per_hour: None,
+            max_bytes_per_hour: None,
+        }
+    }
+}
+
+impl QuotaGuard {
+    /// Fetch the quota again once a snapshot is older than `interval`.
+    pub fn refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Keep at least this many screenshots of the quota in reserve.
+    pub fn min_remaining_screenshots(mut self, screenshots: i64) -> Self {
+        self.min_remaining_screenshots = screenshots;
+        self
+    }
+
+    /// Keep at least this many bytes of bandwidth in reserve.
+    pub fn min_remaining_bytes(mut self, bytes: i64) -> Self {
+        self.min_remaining_bytes = bytes;
+        self
+    }
+
+    /// Allow at most this many screenshots per hour from this process.
+    pub fn max_screenshots_per_hour(mut self, screenshots: u64) -> Self {
+        self.max_screenshots_per_hour = Some(screenshots);
+        self
+    }
+
+    /// Allow at most this many downloaded bytes per hour from this process.
+    pub fn max_bytes_per_hour(mut self, bytes: u64) -> Self {
+        self.max_bytes_per_hour = Some(bytes);
+        self
+    }
+}
+
+/// Shared state of a [`QuotaGuard`] attached to a client and its clones.
+#[derive(Debug)]
+pub(crate) struct QuotaTracker {
+    guard: QuotaGuard,
+    state: Mutex<TrackerState>,
+}
+
+/// Screenshots reserved by [`QuotaTracker::check`] for a request in flight.
+#[derive(Debug)]
+pub(crate) struct Reservation {
+    at: Instant,
+    screenshots: u64,
+    /// Snapshot the reservation was counted against
+    refresh: u64,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    snapshot: Option<(Instant, QuotaStatusResponse)>,
+    /// Number of snapshots fetched so far
+    refreshes: u64,
+    /// Usage since the snapshot was fetched, including reservations
+    screenshots_since_refresh: i64,
+    bytes_since_refresh: i64,
+    /// Usage within the last hour, oldest first
+    window: VecDeque<(Instant, u64, u64)>,
+}
+
+impl TrackerState {
+    fn window_totals(&mut self, now: Instant) -> (u64, u64) {
+        while self.window.front().is_some_and(|(at, _, _)| now.duration_since(*at) >= BUDGET_WINDOW) {
+            self.window.pop_front();
+        }
+        self.window.iter().fold((0, 0), |(s, b), (_, screenshots, bytes)| (s + screenshots, b + bytes))
+    }
+}
+
+impl QuotaTracker {
+    pub(crate) fn new(guard: QuotaGuard) -> Self {
+        Self { guard, state: Mutex::default() }
+    }
+
+    /// Check that `screenshots` more screenshots fit in the quota and the
+    /// hourly budget, refreshing the quota snapshot first if it is stale, and
+    /// reserve them.
+    ///
+    /// Reserving under the same lock keeps concurrent requests from all
+    /// passing against the same remaining quota; [`release`](Self::release)
+    /// the reservation if the request fails. Only one refresh runs at a time.
+    /// If a refresh fails, the previous snapshot keeps being used; without
+    /// one the error is returned.
+    pub(crate) async fn check<F, Fut>(&self, screenshots: u64, fetch: F) -> Result<Reservation, AllscreenshotsError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<QuotaStatusResponse, AllscreenshotsError>>,
+    {
+        let mut state = self.state.lock().await;
+        let now = Instant::now();
+        let stale = state
+            .snapshot
+            .as_ref()
+            .map_or(true, |(fetched, _)| now.duration_since(*fetched) >= self.guard.refresh_interval);
+        if stale {
+            match fetch().await {
+                Ok(quota) => {
+                    state.snapshot = Some((now, quota));
+                    state.refreshes += 1;
+                    state.screenshots_since_refresh = 0;
+                    state.bytes_since_refresh = 0;
+                }
+                Err(e) if state.snapshot.is_none() => return Err(e),
+                Err(_) => {}
+            }
+        }
+
+        if let Some((_, quota)) = &state.snapshot {
+            let remaining = i64::from(quota.screenshots.remaining) - state.screenshots_since_refresh;
+            if remaining - (screenshots as i64) < self.guard.min_remaining_screenshots {
+                return Err(AllscreenshotsError::QuotaExceeded(format!(
+                    "{} screenshot(s) requested, {} remaining with {} kept in reserve",
+                    screenshots, remaining, self.guard.min_remaining_screenshots
+                )));
+            }
+            let remaining_bytes = quota.bandwidth.remaining_bytes - state.bytes_since_refresh;
+            if remaining_bytes < self.guard.min_remaining_bytes {
+                return Err(AllscreenshotsError::QuotaExceeded(format!(
+                    "{} bandwidth byte(s) remaining with {} kept in reserve",
+                    remaining_bytes, self.guard.min_remaining_bytes
+                )));
+            }
+        }
+
+        let (hour_screenshots, hour_bytes) = state.window_totals(now);
+        if let Some(max) = self.guard.max_screenshots_per_hour {
+            if hour_screenshots + screenshots > max {
+                return Err(AllscreenshotsError::QuotaExceeded(format!(
+                    "hourly budget of {} screenshot(s) reached ({} taken)",
+                    max, hour_screenshots
+                )));
+            }
+        }
+        if let Some(max) = self.guard.max_bytes_per_hour {
+            if hour_bytes >= max {
+                return Err(AllscreenshotsError::QuotaExceeded(format!(
+                    "hourly budget of {} byte(s) reached ({} downloaded)",
+                    max, hour_bytes
+                )));
+            }
+        }
+
+        state.screenshots_since_refresh += screenshots as i64;
+        state.window.push_back((now, screenshots, 0));
+        Ok(Reservation { at: now, screenshots, refresh: state.refreshes })
+    }
+
+    /// Give back the screenshots of a request that failed.
+    pub(crate) async fn release(&self, reservation: Reservation) {
+        let mut state = self.state.lock().await;
+        // A newer snapshot started counting from zero and does not include the reservation
+        if state.refreshes == reservation.refresh {
+            state.screenshots_since_refresh -= reservation.screenshots as i64;
+        }
+        let entry = (reservation.at, reservation.screenshots, 0);
+        if let Some(index) = state.window.iter().position(|e| *e == entry) {
+            state.window.remove(index);
+        }
+    }
+
+    /// Count downloaded bytes against the snapshot and the hourly budget.
+    pub(crate) async fn record_bytes(&self, bytes: u64) {
+        let mut state = self.state.lock().await;
+        state.bytes_since_refresh += bytes as i64;
+        state.window.push_back((Instant::now(), 0, bytes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn quota(remaining: i32, remaining_bytes: i64) -> QuotaStatusResponse {
+        serde_json::from_value(json!({
+            "tier": "PRO",
+            "screenshots": {"limit": 1000, "used": 1000 - remaining, "remaining": remaining, "percentUsed": 0},
+            "bandwidth": {
+                "limitBytes": 1_000_000,
+                "limitFormatted": "1 MB",
+                "usedBytes": 1_000_000 - remaining_bytes,
+                "usedFormatted": "",
+                "remainingBytes": remaining_bytes,
+                "remainingFormatted": "",
+                "percentUsed": 0
+            }
+        }))
+        .unwrap()
+    }
+
+    fn is_quota_exceeded(result: Result<Reservation, AllscreenshotsError>) -> bool {
+        matches!(result, Err(AllscreenshotsError::QuotaExceeded(_)))
+    }
+
+    #[tokio::test]
+    async fn test_reserve_counts_local_usage() {
+        let tracker = QuotaTracker::new(QuotaGuard::default().min_remaining_screenshots(2));
+        let fetch = || async { Ok(quota(5, 1000)) };
+
+        assert!(is_quota_exceeded(tracker.check(4, fetch).await));
+        let reservation = tracker.check(3, fetch).await.unwrap();
+
+        // The snapshot is still fresh, so the reservation is what stops the next request
+        let fresh = || async { panic!("snapshot is fresh") };
+        assert!(is_quota_exceeded(tracker.check(1, fresh).await));
+        tracker.release(reservation).await;
+        tracker.check(1, fresh).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_checks_share_the_quota() {
+        let tracker = QuotaTracker::new(QuotaGuard::default());
+        let checks = (0..10).map(|_| {
+            tracker.check(1, || async {
+                tokio::task::yield_now().await;
+                Ok(quota(4, 1000))
+            })
+        });
+        let results = futures::future::join_all(checks).await;
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 4);
+        assert_eq!(results.into_iter().filter(|r| matches!(r, Err(AllscreenshotsError::QuotaExceeded(_)))).count(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_resets_local_usage() {
+        let tracker = QuotaTracker::new(QuotaGuard::default().refresh_interval(Duration::ZERO));
+        let reservation = tracker.check(1, || async { Ok(quota(1, 1000)) }).await.unwrap();
+        tracker.check(1, || async { Ok(quota(1, 1000)) }).await.unwrap();
+        // Releasing a reservation made against an older snapshot does not free quota
+        tracker.release(reservation).await;
+
+        // A failed refresh keeps using the previous snapshot and what was reserved against it
+        assert!(is_quota_exceeded(tracker.check(1, || async { Err(AllscreenshotsError::Timeout) }).await));
+        assert!(is_quota_exceeded(tracker.check(1, || async { Ok(quota(0, 1000)) }).await));
+
+        let tracker = QuotaTracker::new(QuotaGuard::default().min_remaining_bytes(1));
+        assert!(is_quota_exceeded(tracker.check(0, || async { Ok(quota(5, 0)) }).await));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hourly_budget() {
+        let tracker = QuotaTracker::new(
+            QuotaGuard::default().refresh_interval(Duration::MAX).max_screenshots_per_hour(2).max_bytes_per_hour(500),
+        );
+        let fetch = || async { Ok(quota(1000, 1_000_000)) };
+
+        tracker.check(2, fetch).await.unwrap();
+        assert!(is_quota_exceeded(tracker.check(1, fetch).await));
+
+        tokio::time::advance(BUDGET_WINDOW).await;
+        let reservation = tracker.check(2, fetch).await.unwrap();
+        tracker.release(reservation).await;
+        tracker.check(1, fetch).await.unwrap();
+        tracker.record_bytes(500).await;
+        assert!(is_quota_exceeded(tracker.check(0, fetch).await));
+    }
+}
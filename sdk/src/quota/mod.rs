@@ -0,0 +1,7 @@
+//! Client-side quota tracking.
+
+mod guard;
+mod monitor;
+
+pub use guard::*;
+pub use monitor::*;
@@ -0,0 +1,351 @@
+//! Background quota polling with threshold alerts.
+
+use crate::client::AllscreenshotsClient;
+use crate::models::QuotaStatusResponse;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+/// Percentages of the quota that trigger an alert by default.
+pub const DEFAULT_QUOTA_THRESHOLDS: [i32; 3] = [50, 80, 95];
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(300);
+/// Shorter intervals, including zero, are raised to this
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const EVENT_CAPACITY: usize = 16;
+
+/// Callback invoked with every [`QuotaEvent`].
+pub type QuotaEventCallback = Arc<dyn Fn(&QuotaEvent) + Send + Sync>;
+
+/// The part of the quota an alert is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaResource {
+    /// Number of screenshots
+    Screenshots,
+    /// Bandwidth in bytes
+    Bandwidth,
+}
+
+/// Event emitted by a [`QuotaMonitor`].
+#[derive(Debug, Clone)]
+pub enum QuotaEvent {
+    /// Usage reached a threshold for the first time this period
+    ThresholdCrossed {
+        /// Screenshots or bandwidth
+        resource: QuotaResource,
+        /// The threshold, in percent
+        threshold: i32,
+        /// Current usage, in percent
+        percent_used: i32,
+        /// The quota that triggered the alert
+        quota: QuotaStatusResponse,
+    },
+    /// A new quota period started
+    PeriodRollover {
+        /// End of the previous period
+        previous_period_ends: Option<String>,
+        /// The quota of the new period
+        quota: QuotaStatusResponse,
+    },
+}
+
+/// Options for [`AllscreenshotsClient::monitor_quota`].
+#[derive(Clone)]
+pub struct QuotaMonitorOptions {
+    /// Time between two quota checks, at least 10 milliseconds
+    pub interval: Duration,
+    /// Percentages of the quota that trigger an alert
+    pub thresholds: Vec<i32>,
+    /// Called with every event, in addition to the channel
+    pub on_event: Option<QuotaEventCallback>,
+}
+
+impl Default for QuotaMonitorOptions {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_POLL_INTERVAL,
+            thresholds: DEFAULT_QUOTA_THRESHOLDS.to_vec(),
+            on_event: None,
+        }
+    }
+}
+
+impl fmt::Debug for QuotaMonitorOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuotaMonitorOptions")
+            .field("interval", &self.interval)
+            .field("thresholds", &self.thresholds)
+            .field("on_event", &self.on_event.is_some())
+            .finish()
+    }
+}
+
+impl QuotaMonitorOptions {
+    /// Check the quota this often; checks are at least 10 milliseconds apart.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Alert at these percentages of the quota instead of 50, 80 and 95.
+    pub fn thresholds(mut self, thresholds: impl IntoIterator<Item = i32>) -> Self {
+        self.thresholds = thresholds.into_iter().collect();
+        self
+    }
+
+    /// Set a callback invoked with every event.
+    pub fn on_event(mut self, callback: impl Fn(&QuotaEvent) + Send + Sync + 'static) -> Self {
+        self.on_event = Some(Arc::new(callback));
+        self
+    }
+}
+
+/// Handle to a background task polling the quota.
+///
+/// The task stops when the monitor is dropped.
+#[derive(Debug)]
+pub struct QuotaMonitor {
+    sender: broadcast::Sender<QuotaEvent>,
+    /// Receiver created before the task started, handed out by the first `subscribe`
+    first: Mutex<Option<broadcast::Receiver<QuotaEvent>>>,
+    task: JoinHandle<()>,
+}
+
+impl QuotaMonitor {
+    /// Receive the monitor's events.
+    ///
+    /// The first receiver sees every event since the monitor started; later
+    /// ones only see events sent after they subscribed.
+    pub fn subscribe(&self) -> broadcast::Receiver<QuotaEvent> {
+        self.first.lock().unwrap().take().unwrap_or_else(|| self.sender.subscribe())
+    }
+
+    /// Stop polling.
+    pub fn stop(self) {}
+}
+
+impl Drop for QuotaMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl AllscreenshotsClient {
+    /// Poll [`get_quota`](Self::get_quota) in the background and emit a
+    /// [`QuotaEvent`] when usage crosses a threshold or a new period starts.
+    ///
+    /// Each threshold is reported once per period, or again after usage has
+    /// dropped below it. Failed polls are skipped and retried at the next
+    /// interval. Must be called within a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, QuotaEvent, QuotaMonitorOptions};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    ///
+    /// let monitor = client.monitor_quota(QuotaMonitorOptions::default());
+    /// let mut events = monitor.subscribe();
+    /// while let Ok(event) = events.recv().await {
+    ///     if let QuotaEvent::ThresholdCrossed { resource, threshold, .. } = event {
+    ///         println!("{:?} quota passed {}%", resource, threshold);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn monitor_quota(&self, options: QuotaMonitorOptions) -> QuotaMonitor {
+        let (sender, first) = broadcast::channel(EVENT_CAPACITY);
+        let client = self.clone();
+        let events = sender.clone();
+        let task = tokio::spawn(async move {
+            let mut state = AlertState::new(options.thresholds);
+            let mut interval = tokio::time::interval(options.interval.max(MIN_POLL_INTERVAL));
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let Ok(quota) = client.get_quota().await else {
+                    continue;
+                };
+                for event in state.observe(&quota) {
+                    if let Some(callback) = &options.on_event {
+                        callback(&event);
+                    }
+                    // Sending only fails while nobody is subscribed
+                    let _ = events.send(event);
+                }
+            }
+        });
+        QuotaMonitor { sender, first: Mutex::new(Some(first)), task }
+    }
+}
+
+/// Remembers which alerts were already sent, so each is only sent once.
+#[derive(Debug)]
+struct AlertState {
+    /// Ascending, without duplicates
+    thresholds: Vec<i32>,
+    /// `period_ends` of the last poll, `None` before the first one
+    period_ends: Option<Option<String>>,
+    /// Highest threshold already reported per resource
+    screenshots: Option<i32>,
+    bandwidth: Option<i32>,
+}
+
+impl AlertState {
+    fn new(mut thresholds: Vec<i32>) -> Self {
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        Self { thresholds, period_ends: None, screenshots: None, bandwidth: None }
+    }
+
+    /// Events for a freshly polled quota, oldest cause first.
+    fn observe(&mut self, quota: &QuotaStatusResponse) -> Vec<QuotaEvent> {
+        let mut events = Vec::new();
+        match self.period_ends.replace(quota.period_ends.clone()) {
+            Some(previous) if previous != quota.period_ends => {
+                self.screenshots = None;
+                self.bandwidth = None;
+                events.push(QuotaEvent::PeriodRollover { previous_period_ends: previous, quota: quota.clone() });
+            }
+            _ => {}
+        }
+
+        for (resource, percent_used) in [
+            (QuotaResource::Screenshots, quota.screenshots.percent_used),
+            (QuotaResource::Bandwidth, quota.bandwidth.percent_used),
+        ] {
+            let alerted = match resource {
+                QuotaResource::Screenshots => &mut self.screenshots,
+                QuotaResource::Bandwidth => &mut self.bandwidth,
+            };
+            for &threshold in self.thresholds.iter().filter(|t| **t <= percent_used) {
+                if alerted.map_or(true, |a| threshold > a) {
+                    events.push(QuotaEvent::ThresholdCrossed {
+                        resource,
+                        threshold,
+                        percent_used,
+                        quota: quota.clone(),
+                    });
+                }
+            }
+            // Usage can also drop, e.g. after a plan upgrade; alert again when it climbs back
+            *alerted = self.thresholds.iter().rev().copied().find(|t| *t <= percent_used);
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn quota(screenshots: i32, bandwidth: i32, period_ends: &str) -> QuotaStatusResponse {
+        serde_json::from_value(json!({
+            "tier": "PRO",
+            "screenshots": {"limit": 100, "used": screenshots, "remaining": 100 - screenshots, "percentUsed": screenshots},
+            "bandwidth": {
+                "limitBytes": 100, "limitFormatted": "100 B", "usedBytes": bandwidth, "usedFormatted": "",
+                "remainingBytes": 100 - bandwidth, "remainingFormatted": "", "percentUsed": bandwidth
+            },
+            "periodEnds": period_ends
+        }))
+        .unwrap()
+    }
+
+    fn summary(events: &[QuotaEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                QuotaEvent::ThresholdCrossed { resource, threshold, .. } => format!("{:?} {}", resource, threshold),
+                QuotaEvent::PeriodRollover { previous_period_ends, .. } => {
+                    format!("rollover from {}", previous_period_ends.as_deref().unwrap_or("?"))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_alerts_are_deduplicated() {
+        let mut state = AlertState::new(vec![95, 50, 80, 80]);
+        assert!(state.observe(&quota(10, 0, "2024-02-01")).is_empty());
+        assert_eq!(summary(&state.observe(&quota(85, 50, "2024-02-01"))), ["Screenshots 50", "Screenshots 80", "Bandwidth 50"]);
+        assert!(state.observe(&quota(90, 60, "2024-02-01")).is_empty());
+
+        // Dropping below a threshold re-arms it
+        assert!(state.observe(&quota(70, 60, "2024-02-01")).is_empty());
+        assert_eq!(summary(&state.observe(&quota(96, 60, "2024-02-01"))), ["Screenshots 80", "Screenshots 95"]);
+
+        assert_eq!(
+            summary(&state.observe(&quota(60, 0, "2024-03-01"))),
+            ["rollover from 2024-02-01", "Screenshots 50"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_monitor_emits_events() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/usage/quota"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(quota(81, 10, "2024-02-01")))
+            .mount(&server)
+            .await;
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let monitor = client.monitor_quota(
+            QuotaMonitorOptions::default()
+                .interval(Duration::from_millis(10))
+                .on_event(move |event| recorded.lock().unwrap().push(event.clone())),
+        );
+        let mut events = monitor.subscribe();
+        let first = events.recv().await.unwrap();
+        let second = events.recv().await.unwrap();
+        assert_eq!(summary(&[first, second]), ["Screenshots 50", "Screenshots 80"]);
+
+        // Later polls of the same quota do not repeat the alerts
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        monitor.stop();
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_zero_interval_is_raised() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/usage/quota"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(quota(60, 10, "2024-02-01")))
+            .mount(&server)
+            .await;
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let monitor = client.monitor_quota(QuotaMonitorOptions::default().interval(Duration::ZERO));
+        let event = monitor.subscribe().recv().await.unwrap();
+        assert_eq!(summary(&[event]), ["Screenshots 50"]);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(!monitor.task.is_finished());
+    }
+}
@@ -44,7 +44,11 @@ impl RetryConfig {
 
         // Add jitter
         let jitter_range = capped_delay * self.jitter;
-        let jitter = rand::thread_rng().gen_range(-jitter_range..jitter_range);
+        let jitter = if jitter_range > 0.0 {
+            rand::thread_rng().gen_range(-jitter_range..jitter_range)
+        } else {
+            0.0
+        };
         let final_delay = (capped_delay + jitter).max(0.0);
 
         Duration::from_secs_f64(final_delay)
@@ -0,0 +1,315 @@
+//! Exporting schedules to a portable file and importing them into an account.
+
+use crate::client::AllscreenshotsClient;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use chrono::{SecondsFormat, Utc};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Version of the export format written by this SDK.
+pub const SCHEDULE_EXPORT_VERSION: u32 = 1;
+
+const STATUS_PAUSED: &str = "PAUSED";
+
+/// A versioned snapshot of every schedule in an account.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use allscreenshots_sdk::{AllscreenshotsClient, ConflictPolicy};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let old = AllscreenshotsClient::new("old-account-key")?;
+/// old.export_schedules().await?.save("schedules.json")?;
+///
+/// let new = AllscreenshotsClient::new("new-account-key")?;
+/// let report = new.import_schedules_from("schedules.json", ConflictPolicy::Rename).await?;
+/// println!("{} created, {} renamed", report.created.len(), report.renamed.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleExport {
+    /// Format version, see [`SCHEDULE_EXPORT_VERSION`]
+    pub version: u32,
+    /// When the export was taken (RFC 3339)
+    pub exported_at: String,
+    /// The exported schedules
+    pub schedules: Vec<ScheduleResponse>,
+}
+
+impl ScheduleExport {
+    /// Create an export of the given schedules, stamped with the current time.
+    pub fn new(schedules: Vec<ScheduleResponse>) -> Self {
+        Self {
+            version: SCHEDULE_EXPORT_VERSION,
+            exported_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            schedules,
+        }
+    }
+
+    /// Load an export written by [`save`](Self::save).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AllscreenshotsError> {
+        let export: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if export.version != SCHEDULE_EXPORT_VERSION {
+            return Err(AllscreenshotsError::ConfigError(format!(
+                "Unsupported schedule export version {} (expected {})",
+                export.version, SCHEDULE_EXPORT_VERSION
+            )));
+        }
+        Ok(export)
+    }
+
+    /// Write the export as pretty-printed JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AllscreenshotsError> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// What to do when an imported schedule has the same name as an existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Keep the existing schedule and skip the imported one
+    #[default]
+    Skip,
+    /// Update the existing schedule with the imported fields
+    Overwrite,
+    /// Create the imported schedule under a new name, e.g. `Daily (imported)`
+    Rename,
+}
+
+/// Outcome of [`AllscreenshotsClient::import_schedules`], by schedule name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Schedules created under their own name
+    pub created: Vec<String>,
+    /// Existing schedules updated with the imported fields
+    pub overwritten: Vec<String>,
+    /// Imported schedules skipped because the name was taken
+    pub skipped: Vec<String>,
+    /// Schedules created under a new name, as `(original, new)`
+    pub renamed: Vec<(String, String)>,
+}
+
+impl AllscreenshotsClient {
+    /// Export every schedule in the account.
+    pub async fn export_schedules(&self) -> Result<ScheduleExport, AllscreenshotsError> {
+        let schedules = self.list_schedules_stream(ListOptions::new()).try_collect().await?;
+        Ok(ScheduleExport::new(schedules))
+    }
+
+    /// Recreate exported schedules in this account.
+    ///
+    /// Paused schedules are paused again after they are created. Webhook
+    /// secrets are not part of an export and have to be set again. Schedules
+    /// are imported one at a time, stopping at the first error; with
+    /// [`ConflictPolicy::Skip`], running the import again continues where it
+    /// stopped.
+    pub async fn import_schedules(
+        &self,
+        export: &ScheduleExport,
+        policy: ConflictPolicy,
+    ) -> Result<ImportReport, AllscreenshotsError> {
+        let existing: Vec<ScheduleResponse> = self.list_schedules_stream(ListOptions::new()).try_collect().await?;
+        let mut by_name: HashMap<String, ScheduleResponse> =
+            existing.into_iter().map(|s| (s.name.clone(), s)).collect();
+
+        let mut report = ImportReport::default();
+        for schedule in &export.schedules {
+            let mut request = schedule.to_create_request()?;
+            let paused = schedule.status == STATUS_PAUSED;
+
+            let imported = match (by_name.get(&schedule.name), policy) {
+                (None, _) => {
+                    let created = self.create_schedule(&request).await?;
+                    report.created.push(schedule.name.clone());
+                    created
+                }
+                (Some(_), ConflictPolicy::Skip) => {
+                    report.skipped.push(schedule.name.clone());
+                    continue;
+                }
+                (Some(current), ConflictPolicy::Overwrite) => {
+                    let updated = self.update_schedule(&current.id, &request.into()).await?;
+                    report.overwritten.push(schedule.name.clone());
+                    if !paused && updated.status == STATUS_PAUSED {
+                        self.resume_schedule(&updated.id).await?;
+                    }
+                    updated
+                }
+                (Some(_), ConflictPolicy::Rename) => {
+                    request.name = free_name(&schedule.name, &by_name);
+                    let created = self.create_schedule(&request).await?;
+                    report.renamed.push((schedule.name.clone(), request.name));
+                    created
+                }
+            };
+            if paused && imported.status != STATUS_PAUSED {
+                self.pause_schedule(&imported.id).await?;
+            }
+            by_name.insert(imported.name.clone(), imported);
+        }
+        Ok(report)
+    }
+
+    /// Recreate the schedules in an export file written by
+    /// [`ScheduleExport::save`].
+    ///
+    /// See [`import_schedules`](Self::import_schedules).
+    pub async fn import_schedules_from(
+        &self,
+        path: impl AsRef<Path>,
+        policy: ConflictPolicy,
+    ) -> Result<ImportReport, AllscreenshotsError> {
+        self.import_schedules(&ScheduleExport::load(path)?, policy).await
+    }
+}
+
+/// `name (imported)`, or `name (imported 2)` and so on if that is taken too,
+/// kept within the schedule name limit.
+fn free_name(name: &str, taken: &HashMap<String, ScheduleResponse>) -> String {
+    (1..)
+        .map(|n| {
+            let suffix = if n == 1 { " (imported)".to_string() } else { format!(" (imported {})", n) };
+            let base: String = name.chars().take(MAX_SCHEDULE_NAME_LEN - suffix.chars().count()).collect();
+            base + &suffix
+        })
+        .find(|candidate| !taken.contains_key(candidate))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn schedule(id: &str, name: &str, status: &str) -> ScheduleResponse {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": name,
+            "url": "https://example.com",
+            "schedule": "0 9 * * *",
+            "timezone": "Europe/Amsterdam",
+            "status": status,
+            "options": {"fullPage": true, "format": "webp", "hideSelectors": [".banner"]},
+            "retentionDays": 14
+        }))
+        .unwrap()
+    }
+
+    async fn client(server: &MockServer, existing: Vec<ScheduleResponse>) -> AllscreenshotsClient {
+        Mock::given(method("GET"))
+            .and(path("/v1/schedules"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"schedules": existing, "total": 1})))
+            .mount(server)
+            .await;
+        AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schedules.json");
+
+        ScheduleExport::new(vec![schedule("s1", "Daily", "ACTIVE")]).save(&path).unwrap();
+        let loaded = ScheduleExport::load(&path).unwrap();
+        let request = loaded.schedules[0].to_create_request().unwrap();
+        let options = request.options.unwrap();
+        assert_eq!(request.timezone.unwrap().name(), "Europe/Amsterdam");
+        assert_eq!(options.format, Some(ImageFormat::Webp));
+        assert_eq!(options.hide_selectors, Some(vec![".banner".to_string()]));
+
+        let mut future = loaded;
+        future.version = SCHEDULE_EXPORT_VERSION + 1;
+        future.save(&path).unwrap();
+        assert!(matches!(ScheduleExport::load(&path), Err(AllscreenshotsError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_import_renames_conflicts() {
+        let server = MockServer::start().await;
+        let client = client(&server, vec![schedule("s1", "Daily", "ACTIVE")]).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/schedules"))
+            .and(body_partial_json(json!({"name": "Daily (imported)", "options": {"format": "webp"}})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schedule("s2", "Daily (imported)", "ACTIVE")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/schedules"))
+            .and(body_partial_json(json!({"name": "Daily (imported 2)"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schedule("s3", "Daily (imported 2)", "ACTIVE")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/schedules/s3/pause"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schedule("s3", "Daily (imported 2)", "PAUSED")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let export = ScheduleExport::new(vec![schedule("a", "Daily", "ACTIVE"), schedule("b", "Daily", "PAUSED")]);
+        let report = client.import_schedules(&export, ConflictPolicy::Rename).await.unwrap();
+        assert_eq!(
+            report.renamed,
+            vec![
+                ("Daily".to_string(), "Daily (imported)".to_string()),
+                ("Daily".to_string(), "Daily (imported 2)".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_skip_and_overwrite() {
+        let server = MockServer::start().await;
+        let client = client(&server, vec![schedule("s1", "Daily", "PAUSED")]).await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/schedules/s1"))
+            .and(body_partial_json(json!({"name": "Daily", "retentionDays": 14})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schedule("s1", "Daily", "PAUSED")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/schedules/s1/resume"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(schedule("s1", "Daily", "ACTIVE")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let export = ScheduleExport::new(vec![schedule("a", "Daily", "ACTIVE")]);
+        let report = client.import_schedules(&export, ConflictPolicy::Skip).await.unwrap();
+        assert_eq!(report.skipped, vec!["Daily".to_string()]);
+
+        let report = client.import_schedules(&export, ConflictPolicy::Overwrite).await.unwrap();
+        assert_eq!(report.overwritten, vec!["Daily".to_string()]);
+    }
+
+    #[test]
+    fn test_free_name_respects_length_limit() {
+        let long = "x".repeat(MAX_SCHEDULE_NAME_LEN);
+        let taken = HashMap::from([(long.clone(), schedule("s1", &long, "ACTIVE"))]);
+        let name = free_name(&long, &taken);
+        assert_eq!(name.chars().count(), MAX_SCHEDULE_NAME_LEN);
+        assert!(name.ends_with(" (imported)"));
+    }
+}
@@ -0,0 +1,269 @@
+//! Health reports combining a schedule with its recent executions.
+
+use crate::client::AllscreenshotsClient;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use chrono::{DateTime, Duration, Utc};
+use futures::TryStreamExt;
+use std::collections::HashMap;
+
+/// Number of recent executions a health report looks at.
+pub const HEALTH_HISTORY_LIMIT: u32 = 50;
+
+/// How long past `next_execution_at` an active schedule may be before it
+/// counts as overdue.
+const OVERDUE_GRACE: Duration = Duration::minutes(5);
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const STATUS_ACTIVE: &str = "ACTIVE";
+
+/// Health of a schedule, derived from the schedule and its recent executions.
+#[derive(Debug, Clone)]
+pub struct ScheduleHealth {
+    /// The schedule
+    pub schedule: ScheduleResponse,
+    /// Number of recent executions the report is based on
+    pub executions_checked: usize,
+    /// Time of the most recent successful execution
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// The most recent failed execution, with its error
+    pub last_failure: Option<ScheduleExecutionResponse>,
+    /// Failed executions since the last success
+    pub consecutive_failures: u32,
+    /// Most frequent error code among the recent failures
+    pub most_common_error_code: Option<String>,
+    /// Average render time of the recent executions, in milliseconds
+    pub average_render_time_ms: Option<f64>,
+    /// The schedule is active but `next_execution_at` passed without running
+    pub overdue: bool,
+}
+
+impl ScheduleHealth {
+    /// Build a report from a schedule and its recent executions, in any order.
+    pub fn from_history(
+        schedule: ScheduleResponse,
+        executions: &[ScheduleExecutionResponse],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let executed_at = |e: &ScheduleExecutionResponse| {
+            DateTime::parse_from_rfc3339(&e.executed_at).ok().map(|t| t.with_timezone(&Utc))
+        };
+        let mut recent: Vec<_> = executions
+            .iter()
+            .filter_map(|e| Some((executed_at(e)?, e, e.status.parse::<JobStatus>().ok()?)))
+            .collect();
+        recent.sort_by_key(|(t, _, _)| std::cmp::Reverse(*t));
+
+        let last_success_at = recent.iter().find(|(_, _, s)| s.is_success()).map(|(t, _, _)| *t);
+        let failures: Vec<_> = recent
+            .iter()
+            .filter(|(_, _, s)| *s == JobStatus::Failed)
+            .map(|(_, e, _)| *e)
+            .collect();
+        let consecutive_failures = recent
+            .iter()
+            .filter(|(_, _, s)| s.is_terminal() && *s != JobStatus::Cancelled)
+            .take_while(|(_, _, s)| *s == JobStatus::Failed)
+            .count() as u32;
+
+        let mut error_counts: HashMap<&str, usize> = HashMap::new();
+        for code in failures.iter().filter_map(|e| e.error_code.as_deref()) {
+            *error_counts.entry(code).or_default() += 1;
+        }
+        // Failures are newest first and `min_by_key` keeps the first minimum, so ties go to
+        // the error seen most recently
+        let most_common_error_code = failures
+            .iter()
+            .filter_map(|e| e.error_code.as_deref())
+            .min_by_key(|code| std::cmp::Reverse(error_counts[code]))
+            .map(str::to_string);
+
+        let render_times: Vec<i64> = executions.iter().filter_map(|e| e.render_time_ms).collect();
+        let average_render_time_ms = (!render_times.is_empty())
+            .then(|| render_times.iter().sum::<i64>() as f64 / render_times.len() as f64);
+
+        let overdue = schedule.status == STATUS_ACTIVE
+            && schedule.next_execution().is_some_and(|next| next + OVERDUE_GRACE < now);
+
+        Self {
+            executions_checked: recent.len(),
+            last_success_at,
+            last_failure: failures.first().map(|e| (*e).clone()),
+            consecutive_failures,
+            most_common_error_code,
+            average_render_time_ms,
+            overdue,
+            schedule,
+        }
+    }
+
+    /// No failures since the last success, and not overdue.
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures == 0 && !self.overdue
+    }
+}
+
+impl AllscreenshotsClient {
+    /// Report the health of a schedule from its last
+    /// [`HEALTH_HISTORY_LIMIT`] executions.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::AllscreenshotsClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    ///
+    /// let health = client.schedule_health("schedule-id").await?;
+    /// if !health.is_healthy() {
+    ///     println!(
+    ///         "{}: {} failures in a row ({:?}), overdue: {}",
+    ///         health.schedule.name, health.consecutive_failures, health.most_common_error_code, health.overdue
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn schedule_health(&self, schedule_id: &str) -> Result<ScheduleHealth, AllscreenshotsError> {
+        let schedule = self.get_schedule(schedule_id).await?;
+        self.health_of(schedule).await
+    }
+
+    /// Report the health of every schedule in the account, checking several
+    /// schedules at once. Reports are sorted by schedule name.
+    pub async fn all_schedules_health(&self) -> Result<Vec<ScheduleHealth>, AllscreenshotsError> {
+        let mut reports: Vec<ScheduleHealth> = self
+            .list_schedules_stream(ListOptions::new())
+            .map_ok(|schedule| self.health_of(schedule))
+            .try_buffer_unordered(DEFAULT_CONCURRENCY)
+            .try_collect()
+            .await?;
+        reports.sort_by(|a, b| a.schedule.name.cmp(&b.schedule.name));
+        Ok(reports)
+    }
+
+    async fn health_of(&self, schedule: ScheduleResponse) -> Result<ScheduleHealth, AllscreenshotsError> {
+        let options = ScheduleHistoryOptions::new().limit(HEALTH_HISTORY_LIMIT);
+        let history = self.get_schedule_history_with(&schedule.id, &options).await?;
+        Ok(ScheduleHealth::from_history(schedule, &history.executions, Utc::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schedule(id: &str, name: &str, next_execution_at: &str) -> ScheduleResponse {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": name,
+            "url": "https://example.com",
+            "schedule": "0 * * * *",
+            "status": "ACTIVE",
+            "nextExecutionAt": next_execution_at
+        }))
+        .unwrap()
+    }
+
+    fn execution(hour: u32, status: &str, error_code: Option<&str>, render_time_ms: i64) -> ScheduleExecutionResponse {
+        serde_json::from_value(json!({
+            "id": format!("e{}", hour),
+            "executedAt": format!("2024-01-15T{:02}:00:00Z", hour),
+            "status": status,
+            "errorCode": error_code,
+            "renderTimeMs": render_time_ms
+        }))
+        .unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-01-15T12:30:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_failure_streak() {
+        // Deliberately out of order; the cancelled run does not break the streak
+        let executions = [
+            execution(10, "FAILED", Some("NAVIGATION_FAILED"), 900),
+            execution(8, "FAILED", Some("TIMEOUT"), 1000),
+            execution(12, "FAILED", Some("TIMEOUT"), 1100),
+            execution(9, "COMPLETED", None, 500),
+            execution(11, "CANCELLED", None, 0),
+        ];
+        let health = ScheduleHealth::from_history(schedule("s1", "Hourly", "2024-01-15T13:00:00Z"), &executions, now());
+
+        assert_eq!(health.executions_checked, 5);
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!(health.last_success_at.unwrap().to_rfc3339(), "2024-01-15T09:00:00+00:00");
+        assert_eq!(health.last_failure.as_ref().unwrap().id, "e12");
+        assert_eq!(health.most_common_error_code.as_deref(), Some("TIMEOUT"));
+        assert_eq!(health.average_render_time_ms, Some(700.0));
+        assert!(!health.overdue);
+        assert!(!health.is_healthy());
+    }
+
+    #[test]
+    fn test_most_common_error_tie() {
+        let executions = [
+            execution(8, "FAILED", Some("TIMEOUT"), 1000),
+            execution(9, "FAILED", Some("NAVIGATION_FAILED"), 900),
+            execution(10, "FAILED", Some("TIMEOUT"), 1000),
+            execution(11, "FAILED", Some("NAVIGATION_FAILED"), 900),
+        ];
+        let health = ScheduleHealth::from_history(schedule("s1", "Hourly", "2024-01-15T13:00:00Z"), &executions, now());
+        assert_eq!(health.most_common_error_code.as_deref(), Some("NAVIGATION_FAILED"));
+    }
+
+    #[test]
+    fn test_overdue() {
+        let executions = [execution(11, "COMPLETED", None, 500)];
+        let health = ScheduleHealth::from_history(schedule("s1", "Hourly", "2024-01-15T12:00:00Z"), &executions, now());
+        assert!(health.overdue);
+        assert_eq!(health.consecutive_failures, 0);
+
+        let mut paused = schedule("s1", "Hourly", "2024-01-15T12:00:00Z");
+        paused.status = "PAUSED".to_string();
+        assert!(ScheduleHealth::from_history(paused, &executions, now()).is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_all_schedules_health() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let far_future = "2999-01-01T00:00:00Z";
+        Mock::given(method("GET"))
+            .and(path("/v1/schedules"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "schedules": [schedule("s2", "b", far_future), schedule("s1", "a", far_future)],
+                "total": 2
+            })))
+            .mount(&server)
+            .await;
+        for (id, status) in [("s1", "COMPLETED"), ("s2", "FAILED")] {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/schedules/{}/history", id)))
+                .and(query_param("limit", HEALTH_HISTORY_LIMIT.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "scheduleId": id,
+                    "totalExecutions": 1,
+                    "executions": [execution(11, status, None, 100)]
+                })))
+                .mount(&server)
+                .await;
+        }
+
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let reports = client.all_schedules_health().await.unwrap();
+        let summary: Vec<_> = reports.iter().map(|r| (r.schedule.name.as_str(), r.is_healthy())).collect();
+        assert_eq!(summary, [("a", true), ("b", false)]);
+    }
+}
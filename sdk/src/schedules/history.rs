@@ -0,0 +1,191 @@
+//! Paging through and downloading schedule execution history.
+
+use crate::client::{paginate, AllscreenshotsClient};
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use chrono::{DateTime, Utc};
+use futures::stream::{Stream, TryStreamExt};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_EXTENSION: &str = "png";
+
+impl AllscreenshotsClient {
+    /// Stream every execution of a schedule matching `options`, fetching
+    /// pages as needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use allscreenshots_sdk::{AllscreenshotsClient, ScheduleHistoryOptions};
+    /// use futures::TryStreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AllscreenshotsClient::from_env()?;
+    ///
+    /// let options = ScheduleHistoryOptions::new().status("FAILED");
+    /// let mut executions = Box::pin(client.schedule_history_stream("schedule-id", options));
+    /// while let Some(execution) = executions.try_next().await? {
+    ///     println!("{}: {:?}", execution.executed_at, execution.error_message);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn schedule_history_stream(
+        &self,
+        schedule_id: &str,
+        options: ScheduleHistoryOptions,
+    ) -> impl Stream<Item = Result<ScheduleExecutionResponse, AllscreenshotsError>> + Send + 'static {
+        let client = self.clone();
+        let schedule_id = schedule_id.to_string();
+        paginate(options, move |options| {
+            let client = client.clone();
+            let schedule_id = schedule_id.clone();
+            async move {
+                let page = client.get_schedule_history_with(&schedule_id, &options).await?;
+                let next = page.next_options(&options);
+                Ok((page.executions, next))
+            }
+        })
+    }
+
+    /// Download the result of every execution matching `options` into `dir`.
+    ///
+    /// Files are named after the execution time and ID, e.g.
+    /// `20240115T090000Z-exec_123.png`, so they sort chronologically.
+    /// Executions without a `result_url` are skipped, and files that already
+    /// exist are not downloaded again, so an interrupted download can simply
+    /// be rerun. Returns the paths of all result files in the range.
+    pub async fn download_schedule_history(
+        &self,
+        schedule_id: &str,
+        options: ScheduleHistoryOptions,
+        dir: impl AsRef<Path>,
+    ) -> Result<Vec<PathBuf>, AllscreenshotsError> {
+        let dir = dir.as_ref();
+        tokio::fs::create_dir_all(dir).await?;
+
+        let mut paths = Vec::new();
+        let mut executions = Box::pin(self.schedule_history_stream(schedule_id, options));
+        while let Some(execution) = executions.try_next().await? {
+            let Some(url) = execution.result_url.as_deref() else {
+                continue;
+            };
+            let path = dir.join(result_file_name(&execution, url));
+            if !tokio::fs::try_exists(&path).await? {
+                let image = self.download(url).await?;
+                let partial = path.with_extension("part");
+                tokio::fs::write(&partial, &image).await?;
+                tokio::fs::rename(&partial, &path).await?;
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// `<UTC timestamp>-<execution ID>.<extension of the result URL>`, restricted
+/// to characters that are safe in file names on every platform.
+fn result_file_name(execution: &ScheduleExecutionResponse, url: &str) -> String {
+    let timestamp = DateTime::parse_from_rfc3339(&execution.executed_at)
+        .map(|t| t.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string())
+        .unwrap_or_else(|_| sanitize(&execution.executed_at));
+    let extension = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .filter(|ext| !ext.is_empty() && ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| DEFAULT_EXTENSION.to_string());
+    format!("{}-{}.{}", timestamp, sanitize(&execution.id), extension)
+}
+
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn execution(id: &str, executed_at: &str, result_url: Option<&str>) -> serde_json::Value {
+        json!({"id": id, "executedAt": executed_at, "status": "COMPLETED", "resultUrl": result_url})
+    }
+
+    #[test]
+    fn test_result_file_name() {
+        let execution: ScheduleExecutionResponse =
+            serde_json::from_value(execution("e/1", "2024-01-15T10:00:00+01:00", None)).unwrap();
+        let name = |url: &str| result_file_name(&execution, url);
+        assert_eq!(name("https://cdn.example.com/a/shot.JPG?sig=1"), "20240115T090000Z-e_1.jpg");
+        assert_eq!(name("https://cdn.example.com/a/shot"), "20240115T090000Z-e_1.png");
+        assert_eq!(name("https://cdn.example.com/a.b/shot"), "20240115T090000Z-e_1.png");
+    }
+
+    #[tokio::test]
+    async fn test_download_history_range() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/schedules/s1/history"))
+            .and(query_param("executed_after", "2024-01-01T00:00:00Z"))
+            .and(query_param_is_missing("offset"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "scheduleId": "s1",
+                "totalExecutions": 3,
+                "executions": [
+                    execution("e1", "2024-01-15T09:00:00Z", Some("/v1/files/e1.png")),
+                    execution("e2", "2024-01-16T09:00:00Z", None)
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/schedules/s1/history"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "scheduleId": "s1",
+                "totalExecutions": 3,
+                "executions": [execution("e3", "2024-01-17T09:00:00Z", Some("/v1/files/e3.webp"))]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/files/e1.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"first".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/files/e3.webp"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"third".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let after = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let options = ScheduleHistoryOptions::new().executed_after(after);
+
+        let paths = client.download_schedule_history("s1", options.clone(), dir.path()).await.unwrap();
+        let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, ["20240115T090000Z-e1.png", "20240117T090000Z-e3.webp"]);
+        assert_eq!(std::fs::read(&paths[1]).unwrap(), b"third");
+
+        // Rerunning only lists the pages again; existing files are not downloaded twice
+        let again = client.download_schedule_history("s1", options, dir.path()).await.unwrap();
+        assert_eq!(again, paths);
+    }
+}
@@ -0,0 +1,9 @@
+//! Helpers for working with scheduled screenshots.
+
+mod export;
+mod health;
+mod history;
+pub mod sync;
+
+pub use export::*;
+pub use health::*;
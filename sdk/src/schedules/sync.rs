@@ -0,0 +1,633 @@
+//! Declarative sync of schedules from a manifest file ("schedules as code").
+//!
+//! A manifest lists the desired schedules keyed by name, in TOML or JSON, or
+//! in YAML with the `sync-yaml` feature. Field names follow the API
+//! (`retentionDays`, `webhookUrl`, ...):
+//!
+//! ```toml
+//! prune = true
+//!
+//! [schedules.github-daily]
+//! url = "https://github.com"
+//! schedule = "0 9 * * *"
+//! timezone = "Europe/Amsterdam"
+//! options = { fullPage = true }
+//!
+//! [schedules.docs-hourly]
+//! url = "https://docs.rs"
+//! schedule = "0 * * * *"
+//! paused = true
+//! ```
+//!
+//! Schedules are matched to existing ones by name. Fields left out of an
+//! entry are not managed, so values set elsewhere are kept. Schedules that
+//! are not in the manifest are only deleted when `prune` is set.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use allscreenshots_sdk::schedules::sync::ScheduleManifest;
+//! use allscreenshots_sdk::AllscreenshotsClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = AllscreenshotsClient::from_env()?;
+//! let manifest = ScheduleManifest::from_path("schedules.toml")?;
+//!
+//! let plan = manifest.plan(&client).await?;
+//! print!("{}", plan); // dry run
+//! plan.apply(&client).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::AllscreenshotsClient;
+use crate::error::AllscreenshotsError;
+use crate::models::*;
+use crate::validation::{Validate, ValidationErrors};
+use chrono::DateTime;
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+const STATUS_ACTIVE: &str = "ACTIVE";
+const STATUS_PAUSED: &str = "PAUSED";
+
+/// A desired schedule in a manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesiredSchedule {
+    /// The schedule to create, or to update an existing schedule to
+    #[serde(flatten)]
+    pub request: CreateScheduleRequest,
+    /// Keep the schedule paused
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// The desired set of schedules, keyed by name.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleManifest {
+    /// Desired schedules by name
+    pub schedules: BTreeMap<String, DesiredSchedule>,
+    /// Delete existing schedules that are not in the manifest
+    pub prune: bool,
+}
+
+#[derive(Deserialize)]
+struct ManifestFile {
+    #[serde(default)]
+    prune: bool,
+    #[serde(default)]
+    schedules: BTreeMap<String, Value>,
+}
+
+impl ScheduleManifest {
+    /// Load a manifest, choosing the format from the file extension
+    /// (`.toml`, `.json`, or `.yaml`/`.yml` with the `sync-yaml` feature).
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, AllscreenshotsError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            #[cfg(feature = "sync-yaml")]
+            Some("yaml" | "yml") => Self::from_yaml_str(&contents),
+            #[cfg(not(feature = "sync-yaml"))]
+            Some("yaml" | "yml") => Err(AllscreenshotsError::ConfigError(format!(
+                "YAML manifests such as {} need the sync-yaml feature",
+                path.display()
+            ))),
+            Some("toml") => Self::from_toml_str(&contents),
+            Some("json") => Self::from_json_str(&contents),
+            _ => Err(AllscreenshotsError::ConfigError(format!(
+                "Unknown manifest format for {}: expected .yaml, .yml, .toml or .json",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parse a YAML manifest.
+    #[cfg(feature = "sync-yaml")]
+    pub fn from_yaml_str(contents: &str) -> Result<Self, AllscreenshotsError> {
+        let file = serde_yaml::from_str(contents)
+            .map_err(|e| AllscreenshotsError::ConfigError(format!("Invalid YAML manifest: {}", e)))?;
+        Self::from_file(file)
+    }
+
+    /// Parse a TOML manifest.
+    pub fn from_toml_str(contents: &str) -> Result<Self, AllscreenshotsError> {
+        let file = toml::from_str(contents)
+            .map_err(|e| AllscreenshotsError::ConfigError(format!("Invalid TOML manifest: {}", e)))?;
+        Self::from_file(file)
+    }
+
+    /// Parse a JSON manifest.
+    pub fn from_json_str(contents: &str) -> Result<Self, AllscreenshotsError> {
+        let file = serde_json::from_str(contents)
+            .map_err(|e| AllscreenshotsError::ConfigError(format!("Invalid JSON manifest: {}", e)))?;
+        Self::from_file(file)
+    }
+
+    fn from_file(file: ManifestFile) -> Result<Self, AllscreenshotsError> {
+        let mut schedules = BTreeMap::new();
+        let mut errors = ValidationErrors::new();
+        for (name, mut entry) in file.schedules {
+            let path = format!("schedules.{}", name);
+            let Some(fields) = entry.as_object_mut() else {
+                errors.push(path, "must be a table of schedule fields");
+                continue;
+            };
+            match fields.get("name") {
+                None => {
+                    fields.insert("name".to_string(), Value::String(name.clone()));
+                }
+                Some(Value::String(n)) if *n == name => {}
+                Some(_) => {
+                    errors.push(format!("{}.name", path), "must match the key it is listed under");
+                    continue;
+                }
+            }
+            match serde_json::from_value::<DesiredSchedule>(entry) {
+                Ok(desired) => {
+                    desired.request.collect_violations(&path, &mut errors);
+                    schedules.insert(name, desired);
+                }
+                Err(e) => errors.push(path, e.to_string()),
+            }
+        }
+        errors.into_result()?;
+        Ok(Self {
+            schedules,
+            prune: file.prune,
+        })
+    }
+
+    /// Compare the manifest with the account's schedules and work out what
+    /// needs to change. Nothing is modified.
+    pub async fn plan(&self, client: &AllscreenshotsClient) -> Result<SyncPlan, AllscreenshotsError> {
+        let current: Vec<ScheduleResponse> = client.list_schedules_stream(ListOptions::new()).try_collect().await?;
+        SyncPlan::diff(self, &current)
+    }
+}
+
+/// A single change made by a [`SyncPlan`].
+#[derive(Debug, Clone)]
+pub enum SyncAction {
+    /// Create a schedule, pausing it straight away if `paused` is set
+    Create {
+        /// Schedule to create
+        request: CreateScheduleRequest,
+        /// Pause the schedule after creating it
+        paused: bool,
+    },
+    /// Update the changed fields of an existing schedule
+    Update {
+        /// Schedule ID
+        id: String,
+        /// Schedule name
+        name: String,
+        /// Only the fields that differ from the manifest
+        request: UpdateScheduleRequest,
+    },
+    /// Pause an active schedule
+    Pause {
+        /// Schedule ID
+        id: String,
+        /// Schedule name
+        name: String,
+    },
+    /// Resume a paused schedule
+    Resume {
+        /// Schedule ID
+        id: String,
+        /// Schedule name
+        name: String,
+    },
+    /// Delete a schedule that is not in the manifest
+    Delete {
+        /// Schedule ID
+        id: String,
+        /// Schedule name
+        name: String,
+    },
+}
+
+impl fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncAction::Create { request, paused } => {
+                write!(f, "+ create {} ({} at '{}')", request.name, request.url, request.schedule)?;
+                if *paused {
+                    write!(f, ", paused")?;
+                }
+                Ok(())
+            }
+            SyncAction::Update { id, name, request } => {
+                write!(f, "~ update {} [{}]: {}", name, id, changed_fields(request).join(", "))
+            }
+            SyncAction::Pause { id, name } => write!(f, "~ pause {} [{}]", name, id),
+            SyncAction::Resume { id, name } => write!(f, "~ resume {} [{}]", name, id),
+            SyncAction::Delete { id, name } => write!(f, "- delete {} [{}]", name, id),
+        }
+    }
+}
+
+/// The changes needed to bring an account in line with a [`ScheduleManifest`].
+///
+/// `Display` prints one line per action, for a dry run.
+#[derive(Debug, Clone, Default)]
+pub struct SyncPlan {
+    /// Actions in the order they are applied
+    pub actions: Vec<SyncAction>,
+}
+
+impl SyncPlan {
+    /// Work out the actions needed to turn `current` into `manifest`.
+    ///
+    /// Fails if two existing schedules share a name that the manifest
+    /// manages, since it is ambiguous which one to update.
+    pub fn diff(manifest: &ScheduleManifest, current: &[ScheduleResponse]) -> Result<Self, AllscreenshotsError> {
+        let mut by_name: HashMap<&str, &ScheduleResponse> = HashMap::new();
+        for schedule in current {
+            if by_name.insert(&schedule.name, schedule).is_some() && manifest.schedules.contains_key(&schedule.name) {
+                return Err(AllscreenshotsError::ConfigError(format!(
+                    "Several existing schedules are named '{}'; rename or delete all but one before syncing",
+                    schedule.name
+                )));
+            }
+        }
+
+        let mut actions = Vec::new();
+        for (name, desired) in &manifest.schedules {
+            let Some(existing) = by_name.get(name.as_str()) else {
+                actions.push(SyncAction::Create {
+                    request: desired.request.clone(),
+                    paused: desired.paused,
+                });
+                continue;
+            };
+            let request = update_for(&desired.request, existing);
+            if !changed_fields(&request).is_empty() {
+                actions.push(SyncAction::Update {
+                    id: existing.id.clone(),
+                    name: name.clone(),
+                    request,
+                });
+            }
+            let (id, name) = (existing.id.clone(), name.clone());
+            match (desired.paused, existing.status.as_str()) {
+                (true, STATUS_ACTIVE) => actions.push(SyncAction::Pause { id, name }),
+                (false, STATUS_PAUSED) => actions.push(SyncAction::Resume { id, name }),
+                _ => {}
+            }
+        }
+
+        if manifest.prune {
+            for schedule in current {
+                if !manifest.schedules.contains_key(&schedule.name) {
+                    actions.push(SyncAction::Delete {
+                        id: schedule.id.clone(),
+                        name: schedule.name.clone(),
+                    });
+                }
+            }
+        }
+        Ok(Self { actions })
+    }
+
+    /// Whether the account already matches the manifest.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Apply the plan, one action at a time.
+    ///
+    /// Stops at the first failing action; actions before it stay applied.
+    /// Planning again afterwards picks up where this left off.
+    pub async fn apply(&self, client: &AllscreenshotsClient) -> Result<(), AllscreenshotsError> {
+        for action in &self.actions {
+            match action {
+                SyncAction::Create { request, paused } => {
+                    let schedule = client.create_schedule(request).await?;
+                    if *paused {
+                        client.pause_schedule(&schedule.id).await?;
+                    }
+                }
+                SyncAction::Update { id, request, .. } => {
+                    client.update_schedule(id, request).await?;
+                }
+                SyncAction::Pause { id, .. } => {
+                    client.pause_schedule(id).await?;
+                }
+                SyncAction::Resume { id, .. } => {
+                    client.resume_schedule(id).await?;
+                }
+                SyncAction::Delete { id, .. } => client.delete_schedule(id).await?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.actions.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        for action in &self.actions {
+            writeln!(f, "{}", action)?;
+        }
+        Ok(())
+    }
+}
+
+/// An update containing only the managed fields that differ from `existing`.
+fn update_for(desired: &CreateScheduleRequest, existing: &ScheduleResponse) -> UpdateScheduleRequest {
+    fn changed<T: Clone>(desired: Option<&T>, same: impl FnOnce(&T) -> bool) -> Option<T> {
+        desired.filter(|d| !same(d)).cloned()
+    }
+    let same_time = |desired: &String, current: &Option<String>| {
+        let parse = |s: &str| DateTime::parse_from_rfc3339(s).ok();
+        match current.as_deref() {
+            Some(current) => current == desired || (parse(desired).is_some() && parse(desired) == parse(current)),
+            None => false,
+        }
+    };
+
+    UpdateScheduleRequest {
+        name: None,
+        url: changed(Some(&desired.url), |url| *url == existing.url),
+        schedule: changed(Some(&desired.schedule), |cron| *cron == existing.schedule),
+        timezone: changed(desired.timezone.as_ref(), |tz| existing.timezone.as_deref() == Some(tz.name())),
+        options: changed(desired.options.as_ref(), |options| options_match(options, &existing.options)),
+        webhook_url: changed(desired.webhook_url.as_ref(), |url| existing.webhook_url.as_ref() == Some(url)),
+        webhook_secret: None,
+        retention_days: changed(desired.retention_days.as_ref(), |days| existing.retention_days == Some(*days)),
+        starts_at: changed(desired.starts_at.as_ref(), |at| same_time(at, &existing.starts_at)),
+        ends_at: changed(desired.ends_at.as_ref(), |at| same_time(at, &existing.ends_at)),
+    }
+}
+
+/// Every option set in the manifest has the same value on the schedule.
+fn options_match<T: Serialize, U: Serialize>(desired: &T, current: &U) -> bool {
+    let (Ok(Value::Object(desired)), Ok(current)) = (serde_json::to_value(desired), serde_json::to_value(current))
+    else {
+        return false;
+    };
+    desired
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .all(|(key, value)| current.get(key) == Some(value))
+}
+
+/// Names of the fields set in an update, as sent to the API.
+fn changed_fields(request: &UpdateScheduleRequest) -> Vec<String> {
+    match serde_json::to_value(request) {
+        Ok(Value::Object(fields)) => fields.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn existing(id: &str, name: &str, status: &str, extra: Value) -> ScheduleResponse {
+        let mut schedule = json!({
+            "id": id,
+            "name": name,
+            "url": "https://example.com",
+            "schedule": "0 9 * * *",
+            "timezone": "UTC",
+            "status": status,
+            "options": {"fullPage": true, "format": "png"},
+            "retentionDays": 30
+        });
+        schedule.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(schedule).unwrap()
+    }
+
+    const MANIFEST: &str = r#"
+prune = true
+
+[schedules.unchanged]
+url = "https://example.com"
+schedule = "0 9 * * *"
+options = { fullPage = true }
+
+[schedules.changed]
+url = "https://example.com"
+schedule = "0 10 * * *"
+timezone = "Europe/Amsterdam"
+retentionDays = 30
+
+[schedules.paused]
+url = "https://example.com"
+schedule = "0 9 * * *"
+paused = true
+
+[schedules.new]
+url = "https://example.org"
+schedule = "*/30 * * * *"
+"#;
+
+    #[cfg(feature = "sync-yaml")]
+    const YAML: &str = r#"
+prune: true
+schedules:
+  unchanged:
+    url: https://example.com
+    schedule: "0 9 * * *"
+    options:
+      fullPage: true
+  changed:
+    url: https://example.com
+    schedule: "0 10 * * *"
+    timezone: Europe/Amsterdam
+    retentionDays: 30
+  paused:
+    url: https://example.com
+    schedule: "0 9 * * *"
+    paused: true
+  new:
+    url: https://example.org
+    schedule: "*/30 * * * *"
+"#;
+
+    #[test]
+    fn test_formats_are_equivalent() {
+        let toml = ScheduleManifest::from_toml_str(
+            r#"
+prune = true
+[schedules.unchanged]
+url = "https://example.com"
+schedule = "0 9 * * *"
+options = { fullPage = true }
+"#,
+        )
+        .unwrap();
+        let json = ScheduleManifest::from_json_str(
+            r#"{"prune": true, "schedules": {"unchanged": {
+                "url": "https://example.com", "schedule": "0 9 * * *", "options": {"fullPage": true}
+            }}}"#,
+        )
+        .unwrap();
+
+        for manifest in [&toml, &json] {
+            assert!(manifest.prune);
+            let desired = &manifest.schedules["unchanged"];
+            assert_eq!(desired.request.name, "unchanged");
+            assert_eq!(desired.request.options.as_ref().unwrap().full_page, Some(true));
+        }
+        let manifest = ScheduleManifest::from_toml_str(MANIFEST).unwrap();
+        assert_eq!(manifest.schedules.len(), 4);
+        assert!(manifest.schedules["paused"].paused);
+    }
+
+    #[cfg(feature = "sync-yaml")]
+    #[test]
+    fn test_yaml_manifest() {
+        let yaml = ScheduleManifest::from_yaml_str(YAML).unwrap();
+        let toml = ScheduleManifest::from_toml_str(MANIFEST).unwrap();
+        assert_eq!(yaml.prune, toml.prune);
+        assert_eq!(yaml.schedules.keys().collect::<Vec<_>>(), toml.schedules.keys().collect::<Vec<_>>());
+        assert!(yaml.schedules["paused"].paused);
+        assert_eq!(yaml.schedules["changed"].request.retention_days, Some(30));
+    }
+
+    #[cfg(not(feature = "sync-yaml"))]
+    #[test]
+    fn test_yaml_needs_feature() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schedules.yaml");
+        std::fs::write(&path, "schedules: {}").unwrap();
+        assert!(matches!(ScheduleManifest::from_path(&path), Err(AllscreenshotsError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_invalid_manifest_reports_every_entry() {
+        let result = ScheduleManifest::from_toml_str(
+            r#"
+[schedules.bad-cron]
+url = "https://example.com"
+schedule = "every day"
+
+[schedules.renamed]
+name = "other"
+url = "https://example.com"
+schedule = "0 9 * * *"
+"#,
+        );
+        match result {
+            Err(AllscreenshotsError::InvalidRequest(errors)) => {
+                assert!(errors.has_field("schedules.bad-cron.schedule"));
+                assert!(errors.has_field("schedules.renamed.name"));
+            }
+            other => panic!("Expected InvalidRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_plan_only_includes_changes() {
+        let manifest = ScheduleManifest::from_toml_str(MANIFEST).unwrap();
+        let current = vec![
+            existing("s1", "unchanged", "ACTIVE", json!({})),
+            existing("s2", "changed", "PAUSED", json!({})),
+            existing("s3", "paused", "ACTIVE", json!({})),
+            existing("s4", "stale", "ACTIVE", json!({})),
+        ];
+
+        let plan = SyncPlan::diff(&manifest, &current).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "~ update changed [s2]: schedule, timezone\n\
+             ~ resume changed [s2]\n\
+             + create new (https://example.org at '*/30 * * * *')\n\
+             ~ pause paused [s3]\n\
+             - delete stale [s4]\n"
+        );
+        match &plan.actions[0] {
+            SyncAction::Update { request, .. } => {
+                assert_eq!(request.schedule.as_deref(), Some("0 10 * * *"));
+                assert!(request.url.is_none() && request.retention_days.is_none());
+            }
+            other => panic!("Expected an update, got {:?}", other),
+        }
+
+        // Without pruning, unmanaged schedules are left alone
+        let manifest = ScheduleManifest {
+            prune: false,
+            ..manifest
+        };
+        let plan = SyncPlan::diff(&manifest, &current).unwrap();
+        assert!(!plan.actions.iter().any(|a| matches!(a, SyncAction::Delete { .. })));
+    }
+
+    #[test]
+    fn test_plan_rejects_ambiguous_names() {
+        let manifest = ScheduleManifest::from_toml_str(MANIFEST).unwrap();
+        let current = vec![
+            existing("s1", "changed", "ACTIVE", json!({})),
+            existing("s2", "changed", "ACTIVE", json!({})),
+        ];
+        assert!(matches!(SyncPlan::diff(&manifest, &current), Err(AllscreenshotsError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_apply() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let schedules = vec![
+            existing("s1", "changed", "ACTIVE", json!({})),
+            existing("s2", "stale", "ACTIVE", json!({})),
+        ];
+        Mock::given(method("GET"))
+            .and(path("/v1/schedules"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"schedules": schedules, "total": 2})))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/schedules/s1"))
+            .and(body_json(json!({"schedule": "0 10 * * *", "timezone": "Europe/Amsterdam"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&schedules[0]))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/schedules"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(existing("s3", "new", "ACTIVE", json!({}))))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/schedules/s3/pause"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(existing("s3", "paused", "PAUSED", json!({}))))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/schedules/s2"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AllscreenshotsClient::builder()
+            .api_key("test-api-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let mut manifest = ScheduleManifest::from_toml_str(MANIFEST).unwrap();
+        manifest.schedules.remove("unchanged");
+
+        let plan = manifest.plan(&client).await.unwrap();
+        assert_eq!(plan.actions.len(), 4);
+        plan.apply(&client).await.unwrap();
+    }
+}
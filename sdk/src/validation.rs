@@ -0,0 +1,280 @@
+//! Client-side validation of request models.
+//!
+//! Every request type implements [`Validate`], which reports *all* constraint
+//! violations at once together with the path of the offending field, using
+//! the field names sent over the wire (e.g. `output.maxWidth` or
+//! `urls[3].options.quality`). The client validates
+//! each request before sending it, so invalid requests fail fast without a
+//! network round-trip.
+
+use crate::error::AllscreenshotsError;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// A single constraint violation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldViolation {
+    /// Path of the offending field (e.g. `output.columns`)
+    pub field: String,
+    /// Human-readable description of the violation
+    pub message: String,
+}
+
+impl fmt::Display for FieldViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// A collection of constraint violations found while validating a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    violations: Vec<FieldViolation>,
+}
+
+impl ValidationErrors {
+    /// Create an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a violation for the given field path.
+    pub fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.violations.push(FieldViolation {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Returns `true` if no violations were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Number of recorded violations.
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    /// Iterate over the recorded violations.
+    pub fn iter(&self) -> impl Iterator<Item = &FieldViolation> {
+        self.violations.iter()
+    }
+
+    /// Returns `true` if a violation was recorded for the given field path.
+    pub fn has_field(&self, field: &str) -> bool {
+        self.violations.iter().any(|v| v.field == field)
+    }
+
+    /// Convert into a `Result`, failing if any violation was recorded.
+    pub fn into_result(self) -> Result<(), AllscreenshotsError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(AllscreenshotsError::InvalidRequest(self))
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = FieldViolation;
+    type IntoIter = std::vec::IntoIter<FieldViolation>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.violations.into_iter()
+    }
+}
+
+/// Client-side validation for request models.
+///
+/// # Example
+///
+/// ```rust
+/// use allscreenshots_sdk::{BulkRequest, BulkUrlRequest, Validate};
+///
+/// let request = BulkRequest::new(vec![BulkUrlRequest::new("ftp://example.com")]);
+/// let errors = request.violations();
+/// assert!(errors.has_field("urls[0].url"));
+/// ```
+pub trait Validate {
+    /// Record every violation in `self`, prefixing field names with `path`.
+    ///
+    /// `path` is empty for the top-level request.
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors);
+
+    /// Return every violation found in `self`.
+    fn violations(&self) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        self.collect_violations("", &mut errors);
+        errors
+    }
+
+    /// Validate `self`, returning [`AllscreenshotsError::InvalidRequest`] if any
+    /// constraint is violated.
+    fn validate(&self) -> Result<(), AllscreenshotsError> {
+        self.violations().into_result()
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn collect_violations(&self, path: &str, errors: &mut ValidationErrors) {
+        if let Some(value) = self {
+            value.collect_violations(path, errors);
+        }
+    }
+}
+
+/// Join a parent path and a field name (`output` + `columns` -> `output.columns`).
+pub(crate) fn field_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/// Path of the element at `index` within a list field (`urls` + 3 -> `urls[3]`).
+pub(crate) fn index_path(path: &str, field: &str, index: usize) -> String {
+    format!("{}[{}]", field_path(path, field), index)
+}
+
+/// Check that an optional integer lies in the inclusive range.
+pub(crate) fn check_range(
+    errors: &mut ValidationErrors,
+    path: &str,
+    field: &str,
+    value: Option<i32>,
+    range: RangeInclusive<i32>,
+) {
+    if let Some(value) = value {
+        if !range.contains(&value) {
+            errors.push(
+                field_path(path, field),
+                format!("must be between {} and {}", range.start(), range.end()),
+            );
+        }
+    }
+}
+
+/// Check that a required URL is present and uses http or https.
+pub(crate) fn check_http_url(errors: &mut ValidationErrors, path: &str, field: &str, value: &str) {
+    if value.is_empty() {
+        errors.push(field_path(path, field), "is required");
+    } else if !value.starts_with("http://") && !value.starts_with("https://") {
+        errors.push(field_path(path, field), "must start with http:// or https://");
+    }
+}
+
+/// Check an optional URL (e.g. a webhook) if it is set.
+pub(crate) fn check_optional_http_url(
+    errors: &mut ValidationErrors,
+    path: &str,
+    field: &str,
+    value: Option<&str>,
+) {
+    if let Some(value) = value {
+        check_http_url(errors, path, field, value);
+    }
+}
+
+/// Check that a list has between 1 and `max` entries.
+pub(crate) fn check_list_len<T>(
+    errors: &mut ValidationErrors,
+    path: &str,
+    field: &str,
+    items: &[T],
+    max: usize,
+) {
+    if items.is_empty() {
+        errors.push(field_path(path, field), "must contain at least one entry");
+    } else if items.len() > max {
+        errors.push(
+            field_path(path, field),
+            format!("must contain at most {} entries (got {})", max, items.len()),
+        );
+    }
+}
+
+/// Check that an optional integer is at least `min`.
+pub(crate) fn check_min(errors: &mut ValidationErrors, path: &str, field: &str, value: Option<i32>, min: i32) {
+    if let Some(value) = value {
+        if value < min {
+            errors.push(field_path(path, field), format!("must be at least {}", min));
+        }
+    }
+}
+
+/// Record an error raised while converting a builder argument.
+pub(crate) fn record_error(errors: &mut ValidationErrors, path: &str, field: &str, error: AllscreenshotsError) {
+    match error {
+        AllscreenshotsError::ValidationError(message) => errors.push(field_path(path, field), message),
+        other => errors.push(field_path(path, field), other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_paths() {
+        assert_eq!(field_path("", "url"), "url");
+        assert_eq!(field_path("output", "columns"), "output.columns");
+        assert_eq!(index_path("", "urls", 3), "urls[3]");
+        assert_eq!(index_path("request", "captures", 0), "request.captures[0]");
+    }
+
+    #[test]
+    fn test_check_range() {
+        let mut errors = ValidationErrors::new();
+        check_range(&mut errors, "", "quality", Some(50), 1..=100);
+        check_range(&mut errors, "", "quality", None, 1..=100);
+        assert!(errors.is_empty());
+
+        check_range(&mut errors, "output", "columns", Some(11), 1..=10);
+        assert!(errors.has_field("output.columns"));
+    }
+
+    #[test]
+    fn test_check_min() {
+        let mut errors = ValidationErrors::new();
+        check_min(&mut errors, "", "width", Some(0), 0);
+        check_min(&mut errors, "", "width", None, 0);
+        assert!(errors.is_empty());
+
+        check_min(&mut errors, "border", "width", Some(-1), 0);
+        assert!(errors.has_field("border.width"));
+    }
+
+    #[test]
+    fn test_display_lists_all_violations() {
+        let mut errors = ValidationErrors::new();
+        errors.push("url", "is required");
+        errors.push("quality", "must be between 1 and 100");
+        assert_eq!(errors.to_string(), "url: is required; quality: must be between 1 and 100");
+    }
+
+    #[test]
+    fn test_into_result() {
+        assert!(ValidationErrors::new().into_result().is_ok());
+
+        let mut errors = ValidationErrors::new();
+        errors.push("url", "is required");
+        match errors.into_result() {
+            Err(AllscreenshotsError::InvalidRequest(errors)) => assert_eq!(errors.len(), 1),
+            other => panic!("Expected InvalidRequest, got {:?}", other),
+        }
+    }
+}
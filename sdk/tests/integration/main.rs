@@ -2,7 +2,7 @@
 //!
 //! These tests require the `ALLSCREENSHOTS_API_KEY` environment variable to be set.
 
-use allscreenshots_sdk::{AllscreenshotsClient, ScreenshotRequest, AllscreenshotsError};
+use allscreenshots_sdk::{AllscreenshotsClient, ScreenshotRequest};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use std::env;
 use std::fs::File;
@@ -110,13 +110,13 @@ fn test_create_schedule_request() {
         "https://example.com",
         "0 9 * * *",
     )
-    .with_timezone("America/New_York")
+    .with_timezone(Timezone::parse("America/New_York").unwrap())
     .with_retention_days(30);
 
     assert_eq!(schedule.name, "Daily capture");
     assert_eq!(schedule.url, "https://example.com");
     assert_eq!(schedule.schedule, "0 9 * * *");
-    assert_eq!(schedule.timezone, Some("America/New_York".to_string()));
+    assert_eq!(schedule.timezone.map(|tz| tz.name()), Some("America/New_York"));
     assert_eq!(schedule.retention_days, Some(30));
 }
 